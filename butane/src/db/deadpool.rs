@@ -4,15 +4,29 @@
 use crate::Result;
 use deadpool::managed::{Manager, Metrics, RecycleError, RecycleResult};
 
+/// A [deadpool] pool of [ConnectionAsync]s.
+pub type Pool = deadpool::managed::Pool<ConnectionManager>;
+
+/// A connection checked out from a [Pool].
+pub type PooledConnection = deadpool::managed::Object<ConnectionManager>;
+
 impl Manager for ConnectionManager {
     type Type = ConnectionAsync;
     type Error = crate::Error;
 
+    /// Creates a connection using the backend's native async
+    /// implementation, without going through a sync adapter.
     async fn create(&self) -> Result<ConnectionAsync> {
         crate::db::connect_async(&self.spec).await
     }
 
+    /// Pings the connection before handing it out again, discarding it
+    /// if the ping fails or the connection has been closed.
     async fn recycle(&self, conn: &mut ConnectionAsync, _: &Metrics) -> RecycleResult<Self::Error> {
+        if conn.is_closed() {
+            return Err(RecycleError::message("Connection is closed"));
+        }
+        conn.execute("SELECT 1").await?;
         if conn.is_closed() {
             return Err(RecycleError::message("Connection is closed"));
         }
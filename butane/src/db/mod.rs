@@ -19,6 +19,8 @@
 
 #[cfg(feature = "deadpool")]
 mod deadpool;
+#[cfg(feature = "deadpool")]
+pub use deadpool::{Pool, PooledConnection};
 
 /// Connection manager used with connection pooling systems such as r2d2 or deadpool.
 /// With the `r2d2` feature enabled, it implements `r2d2::ManageConnection`.
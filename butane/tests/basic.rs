@@ -6,7 +6,7 @@
 use butane_test_helper::*;
 use butane_test_macros::butane_test;
 #[cfg(feature = "datetime")]
-use chrono::{naive::NaiveDateTime, offset::Utc, DateTime};
+use chrono::{naive::NaiveDate, naive::NaiveDateTime, offset::Utc, DateTime};
 #[cfg(feature = "sqlite")]
 use rusqlite;
 use serde::Serialize;
@@ -85,6 +85,14 @@ struct HasOnlyAutoPk {
     id: AutoPk<i64>,
 }
 
+#[model]
+#[derive(Debug, Default, PartialEq, Clone)]
+struct Counter {
+    id: i64,
+    count: u64,
+    limit: Option<u64>,
+}
+
 #[model]
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct SelfReferential {
@@ -108,6 +116,7 @@ struct TimeHolder {
     pub naive: NaiveDateTime,
     pub utc: DateTime<Utc>,
     pub when: chrono::DateTime<Utc>,
+    pub day: NaiveDate,
 }
 
 #[butane_test]
@@ -345,6 +354,36 @@ async fn basic_unique_field_error_on_non_unique(conn: ConnectionAsync) {
     });
 }
 
+#[butane_test]
+async fn basic_u64(conn: ConnectionAsync) {
+    let mut counter = Counter {
+        id: 1,
+        count: i64::MAX as u64,
+        limit: Some(7),
+    };
+    counter.save(&conn).await.unwrap();
+    assert_eq!(Counter::get(&conn, 1).await.unwrap(), counter);
+
+    let big = query!(Counter, count > 1000u64).load(&conn).await.unwrap();
+    assert_eq!(big, vec![counter.clone()]);
+
+    // Values which don't fit in a BigInt are rejected rather than wrapped.
+    counter.count = u64::MAX;
+    let e = counter.save(&conn).await.unwrap_err();
+    assert!(matches!(e, butane::Error::OutOfRange), "{e:?}");
+    counter.count = 1;
+    counter.limit = Some(i64::MAX as u64 + 1);
+    let e = counter.save(&conn).await.unwrap_err();
+    assert!(matches!(e, butane::Error::OutOfRange), "{e:?}");
+
+    // As are negative values read from the database.
+    conn.execute("UPDATE Counter SET count = -1 WHERE id = 1")
+        .await
+        .unwrap();
+    let e = Counter::get(&conn, 1).await.unwrap_err();
+    assert!(matches!(e, butane::Error::OutOfRange), "{e:?}");
+}
+
 #[butane_test]
 async fn fkey_same_type(conn: ConnectionAsync) {
     let mut o1 = SelfReferential::new(1);
@@ -376,6 +415,7 @@ async fn basic_time(conn: ConnectionAsync) {
         naive: now.naive_utc(),
         utc: now,
         when: now,
+        day: now.date_naive(),
     };
     time.save(&conn).await.unwrap();
 
@@ -383,6 +423,7 @@ async fn basic_time(conn: ConnectionAsync) {
     // Note, we don't just compare the objects directly because we
     // lose some precision when we go to the database.
     assert_eq!(time.utc.timestamp(), time2.utc.timestamp());
+    assert_eq!(time.day, time2.day);
 }
 
 #[butane_test]
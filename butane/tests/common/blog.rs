@@ -34,6 +34,7 @@ pub struct Post {
     pub id: i64,
     pub title: String,
     pub body: String,
+    #[index]
     pub published: bool,
     pub pub_time: std::option::Option<NaiveDateTime>,
     pub likes: i32,
@@ -49,6 +50,7 @@ pub struct Post {
     pub id: i64,
     pub title: String,
     pub body: String,
+    #[index]
     pub published: bool,
     pub likes: i32,
     pub tags: Many<Tag>,
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use butane::db::ConnectionAsync;
+use butane::{model, query};
+use butane_test_helper::*;
+use butane_test_macros::butane_test;
+use rust_decimal::Decimal;
+
+#[model]
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Price {
+    id: i64,
+    amount: Decimal,
+    discount: Option<rust_decimal::Decimal>,
+}
+impl Price {
+    fn new(id: i64, amount: &str) -> Self {
+        Price {
+            id,
+            amount: Decimal::from_str(amount).unwrap(),
+            discount: None,
+        }
+    }
+}
+
+#[butane_test]
+async fn decimal_roundtrip(conn: ConnectionAsync) {
+    let amounts = [
+        "0",
+        "1.50",
+        "-12.345000",
+        "0.0000000000000000000000000001",
+        "79228162514264337593543950335",
+    ];
+    for (id, amount) in amounts.iter().enumerate() {
+        let mut price = Price::new(id as i64, amount);
+        price.discount = Some(Decimal::from_str("0.10").unwrap());
+        price.save(&conn).await.unwrap();
+
+        let loaded = Price::get(&conn, id as i64).await.unwrap();
+        assert_eq!(loaded, price);
+        // Decimal equality ignores scale, so check it separately.
+        assert_eq!(loaded.amount.to_string(), *amount);
+        assert_eq!(loaded.amount.scale(), price.amount.scale());
+        assert_eq!(loaded.discount.unwrap().to_string(), "0.10");
+    }
+}
+
+#[butane_test]
+async fn decimal_query(conn: ConnectionAsync) {
+    let mut price = Price::new(1, "19.99");
+    price.save(&conn).await.unwrap();
+    Price::new(2, "5.25").save(&conn).await.unwrap();
+
+    let amount = Decimal::from_str("19.99").unwrap();
+    let found = query!(Price, amount == { amount })
+        .load(&conn)
+        .await
+        .unwrap();
+    assert_eq!(found, vec![price]);
+}
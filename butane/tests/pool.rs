@@ -1,3 +1,4 @@
+use butane::db::BackendConnectionAsync;
 #[cfg(any(feature = "pg", feature = "sqlite"))]
 use butane::db::ConnectionManager;
 use butane_test_helper::*;
@@ -64,3 +65,33 @@ async fn deadpool_test_pg_async() {
     assert_eq!(pool.status().size, 1);
     assert_eq!(pool.status().available, 1);
 }
+
+#[tokio::test]
+async fn deadpool_discards_broken_pg_connection() {
+    let (connspec, _data) = pg_connspec().await;
+    let pool = butane::db::Pool::builder(ConnectionManager::new(connspec.clone()))
+        .max_size(1)
+        .build()
+        .unwrap();
+    {
+        let conn = pool.get().await.unwrap();
+        conn.execute("SELECT 1").await.unwrap();
+    }
+    assert_eq!(pool.status().available, 1);
+
+    // Kill the pooled connection's backend from a separate connection.
+    let killer = butane::db::connect_async(&connspec).await.unwrap();
+    killer
+        .execute(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+             WHERE datname = current_database() AND pid <> pg_backend_pid()",
+        )
+        .await
+        .unwrap();
+
+    // Recycling fails, so the pool hands out a fresh connection instead.
+    let conn = pool.get().await.unwrap();
+    assert!(!conn.is_closed());
+    conn.execute("SELECT 1").await.unwrap();
+    assert_eq!(pool.status().size, 1);
+}
@@ -1,5 +1,5 @@
 use butane::db::{Connection, ConnectionAsync};
-use butane::query::BoolExpr;
+use butane::query::{BoolExpr, OrderDirection};
 use butane::{colname, filter, find, find_async, query, Many};
 use butane_test_helper::*;
 use butane_test_macros::butane_test;
@@ -49,6 +49,32 @@ async fn ordered(conn: ConnectionAsync) {
     assert_eq!(posts[2].title, "The Tiger");
 }
 
+#[butane_test]
+async fn ordered_by_many_count(conn: ConnectionAsync) {
+    blog::setup_blog(&conn).await;
+    let posts = Post::query()
+        .order_by(Post::fields().tags().count(), OrderDirection::Descending)
+        .order_asc(colname!(Post, title))
+        .load(&conn)
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 4);
+    assert_eq!(posts[0].title, "The Tiger");
+    assert_eq!(posts[1].title, "Mount Doom");
+    assert_eq!(posts[2].title, "Mt. Everest");
+    assert_eq!(posts[3].title, "Sir Charles");
+
+    let posts = query!(Post, published == true)
+        .order_by(Post::fields().tags().count(), OrderDirection::Ascending)
+        .load(&conn)
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 3);
+    assert_eq!(posts[0].title, "Sir Charles");
+    assert_eq!(posts[1].title, "Mount Doom");
+    assert_eq!(posts[2].title, "The Tiger");
+}
+
 #[butane_test]
 async fn comparison(conn: ConnectionAsync) {
     blog::setup_blog(&conn).await;
@@ -0,0 +1,72 @@
+use butane::db::ConnectionAsync;
+use butane::{model, query};
+use butane_test_helper::*;
+use butane_test_macros::butane_test;
+use time::macros::{date, datetime};
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+#[model]
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Appointment {
+    id: i64,
+    day: Date,
+    local: PrimitiveDateTime,
+    at: time::OffsetDateTime,
+    ends: Option<Date>,
+}
+
+#[butane_test]
+async fn time_roundtrip(conn: ConnectionAsync) {
+    let mut appt = Appointment {
+        id: 1,
+        day: date!(2024 - 02 - 29),
+        local: datetime!(1999-12-31 23:59:59),
+        at: datetime!(2024-06-01 12:30:00 UTC),
+        ends: None,
+    };
+    appt.save(&conn).await.unwrap();
+    assert_eq!(Appointment::get(&conn, 1).await.unwrap(), appt);
+
+    appt.ends = Some(date!(2024 - 03 - 01));
+    appt.save(&conn).await.unwrap();
+    assert_eq!(Appointment::get(&conn, 1).await.unwrap(), appt);
+}
+
+#[butane_test]
+async fn time_offset_stored_as_utc(conn: ConnectionAsync) {
+    let at: OffsetDateTime = datetime!(2024-06-01 14:30:00 +02:00);
+    let mut appt = Appointment {
+        id: 1,
+        day: date!(2024 - 06 - 01),
+        local: datetime!(2024-06-01 14:30:00),
+        at,
+        ends: None,
+    };
+    appt.save(&conn).await.unwrap();
+
+    let loaded = Appointment::get(&conn, 1).await.unwrap();
+    assert_eq!(loaded.at, at);
+    assert!(loaded.at.offset().is_utc());
+    assert_eq!(loaded.at.hour(), 12);
+}
+
+#[butane_test]
+async fn time_query(conn: ConnectionAsync) {
+    for (id, day) in [(1, date!(2024 - 01 - 01)), (2, date!(2024 - 07 - 01))] {
+        let mut appt = Appointment {
+            id,
+            day,
+            local: day.midnight(),
+            at: day.midnight().assume_utc(),
+            ends: None,
+        };
+        appt.save(&conn).await.unwrap();
+    }
+    let cutoff = date!(2024 - 03 - 01);
+    let found = query!(Appointment, day > { cutoff })
+        .load(&conn)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, 2);
+}
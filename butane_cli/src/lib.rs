@@ -14,7 +14,7 @@
 };
 
 use butane::db::Backend;
-use butane::db::{Connection, ConnectionMethods};
+use butane::db::{BackendConnection, Connection, ConnectionMethods};
 use butane::migrations::adb;
 use butane::migrations::adb::{diff, AColumn, ARef, Operation, ADB};
 use butane::migrations::{
@@ -87,7 +87,14 @@ pub fn make_migration(base_dir: &Path, name: Option<&String>) -> Result<()> {
     }
     let backends = load_backends(base_dir)?;
 
-    let created = ms.create_migration(&backends, &name, ms.latest().as_ref())?;
+    let latest = ms.latest();
+    for table in ms.missing_tables(latest.as_ref())? {
+        println!(
+            "Table {table} has no model in the current build and is retained. \
+             If its model was removed, run `butane delete table {table}` to drop it."
+        );
+    }
+    let created = ms.create_migration(&backends, &name, latest.as_ref())?;
     if created {
         update_embedded(base_dir)?;
         println!("Created migration {name}");
@@ -117,6 +124,9 @@ pub fn print_column_diff(old: &AColumn, new: &AColumn) -> Result<()> {
     if old.default() != new.default() {
         println!("  default: {:?} -> {:?}", old.default(), new.default());
     }
+    if old.check() != new.check() {
+        println!("  check: {:?} -> {:?}", old.check(), new.check());
+    }
     if old.reference() != new.reference() {
         let old = match old.reference() {
             Some(ARef::Literal(reference)) => {
@@ -174,6 +184,20 @@ pub fn print_ops(ops: Vec<Operation>) -> Result<()> {
                 println!("Change column {}.{column_name}", table_name);
                 print_column_diff(old, new)?;
             }
+            ChangeTableChecks(table_name, old, new) => {
+                println!("Change checks on {table_name}");
+                println!("  checks: {:?} -> {:?}", old, new);
+            }
+            AddIndex(table_name, index) => {
+                println!(
+                    "New index {} on {table_name} ({})",
+                    index.name,
+                    index.columns.join(", ")
+                );
+            }
+            RemoveIndex(table_name, index_name) => {
+                println!("Remove index {index_name} from {table_name}");
+            }
         }
     }
     Ok(())
@@ -182,8 +206,9 @@ pub fn print_ops(ops: Vec<Operation>) -> Result<()> {
 /// Print a description of the current migration.
 pub fn describe_current_migration(base_dir: &Path) -> Result<()> {
     let mut ms = get_migrations(base_dir)?;
-    let to_db = ms.current().db()?;
-    let from_db = if let Some(latest) = ms.latest() {
+    let latest = ms.latest();
+    let to_db = ms.current_db_from(latest.as_ref())?;
+    let from_db = if let Some(latest) = latest {
         latest.db()?
     } else {
         ADB::new()
@@ -261,14 +286,20 @@ pub fn detach_latest_migration(base_dir: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-pub fn migrate(base_dir: &PathBuf, name: Option<String>) -> Result<()> {
+/// Apply unapplied migrations, up to and including `name` if given.
+/// With `dry_run`, the SQL which would be run is printed instead.
+pub fn migrate(base_dir: &PathBuf, name: Option<String>, dry_run: bool) -> Result<()> {
     let spec = load_connspec(base_dir)?;
     let mut conn = db::connect(&spec)?;
     let to_apply = get_migrations(base_dir)?.unapplied_migrations(&conn)?;
     println!("{} migrations to apply", to_apply.len());
     for m in to_apply {
-        println!("Applying migration {}", m.name());
-        m.apply(&mut conn)?;
+        if dry_run {
+            print_migration_sql(&m, conn.backend_name())?;
+        } else {
+            println!("Applying migration {}", m.name());
+            m.apply(&mut conn)?;
+        }
         if let Some(ref name) = name {
             if name == &m.name().to_string() {
                 println!("Finishing at migration {}", m.name());
@@ -279,6 +310,204 @@ pub fn migrate(base_dir: &PathBuf, name: Option<String>) -> Result<()> {
     Ok(())
 }
 
+/// Print the SQL run when applying migration `m` to backend `backend_name`.
+fn print_migration_sql(m: &impl Migration, backend_name: &str) -> Result<()> {
+    let sql = m
+        .up_sql(backend_name)?
+        .ok_or_else(|| anyhow::anyhow!("Migration {} has no {backend_name} SQL", m.name()))?;
+    println!("-- Migration {}", m.name());
+    println!("{}", sql.trim_end());
+    if let Some(data_sql) = m.up_data_sql()? {
+        println!("{}", data_sql.trim_end());
+    }
+    Ok(())
+}
+
+/// Compare the database schema against that of the last applied migration.
+pub fn verify(base_dir: &PathBuf) -> Result<()> {
+    let spec = load_connspec(base_dir)?;
+    let conn = db::connect(&spec)?;
+    let Some(m) = get_migrations(base_dir)?.last_applied_migration(&conn)? else {
+        println!("No migrations have been applied");
+        return Ok(());
+    };
+    let discrepancies = migrations::verify_schema(&conn, &m.db()?)?;
+    if discrepancies.is_empty() {
+        println!("Database matches migration {}", m.name());
+        return Ok(());
+    }
+    for discrepancy in &discrepancies {
+        eprintln!("{discrepancy}");
+    }
+    Err(anyhow::anyhow!(
+        "Database differs from migration {} in {} places",
+        m.name(),
+        discrepancies.len()
+    ))
+}
+
+/// Generate models for the tables of an existing database, along with
+/// an initial migration matching them. As the tables already exist,
+/// the migration is marked as applied.
+pub fn introspect(base_dir: &PathBuf, output: Option<&Path>) -> Result<()> {
+    let spec = load_connspec(base_dir)?;
+    let conn = db::connect(&spec)?;
+    let mut ms = migrations::from_root(base_dir.join("migrations"));
+    if ms.latest().is_some() {
+        return Err(anyhow::anyhow!(
+            "Migrations already exist, so the database is already managed by butane"
+        ));
+    }
+    let backend = conn.backend();
+    let mut schema = backend.read_schema(&conn)?;
+    let source = models_source(&mut schema);
+
+    let name = format!("{}_init", default_name());
+    let backends = nonempty::nonempty![conn.backend()];
+    if !ms.create_migration_to(&backends, &name, None, schema)? {
+        println!("No tables found");
+        return Ok(());
+    }
+    let migrations_sql = backend.create_migration_sql(
+        &ADB::new(),
+        vec![Operation::AddTableIfNotExists(
+            migrations::migrations_table(),
+        )],
+    )?;
+    conn.execute(&migrations_sql)?;
+    ms.latest().unwrap().mark_applied(&conn)?;
+    update_embedded(base_dir)?;
+    println!("Created migration {name} and marked it as applied");
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, source)?;
+            println!("Wrote models to {}", path.display());
+        }
+        None => print!("{source}"),
+    }
+    Ok(())
+}
+
+/// Generate the source of `#[model]` structs for the tables of
+/// `schema`. Tables without a primary key, and columns whose name or
+/// type has no Rust equivalent, are removed from `schema` and noted
+/// in comments instead.
+pub fn models_source(schema: &mut ADB) -> String {
+    let mut models = Vec::new();
+    let mut uses_auto = false;
+    let tables: Vec<adb::ATable> = schema.tables().cloned().collect();
+    for mut table in tables {
+        let struct_name = struct_name(&table.name);
+        if table.pk().is_none() || !is_identifier(&struct_name) {
+            schema.remove_table(&table.name);
+            models.push(format!(
+                "// Table `{}` was omitted, as it has no primary key or its name is not supported.\n",
+                table.name
+            ));
+            continue;
+        }
+        let mut model = String::from("#[model]\n");
+        if struct_name != table.name {
+            model.push_str(&format!("#[table = \"{}\"]\n", table.name));
+        }
+        model.push_str(&format!(
+            "#[derive(Debug, Default)]\npub struct {struct_name} {{\n"
+        ));
+        for col in std::mem::take(&mut table.columns) {
+            let rust_type = match col.typeid() {
+                Ok(adb::TypeIdentifier::Ty(ty)) => rust_type(&ty),
+                _ => None,
+            };
+            let Some(mut rust_type) = rust_type.filter(|_| is_identifier(col.name())) else {
+                model.push_str(&format!(
+                    "    // Column `{}` was omitted, as its name or type is not supported.\n",
+                    col.name()
+                ));
+                continue;
+            };
+            if col.is_pk() && col.name() != "id" {
+                model.push_str("    #[pk]\n");
+            }
+            if col.is_auto() {
+                rust_type = format!("AutoPk<{rust_type}>");
+                uses_auto = true;
+            } else if col.nullable() {
+                rust_type = format!("Option<{rust_type}>");
+            }
+            model.push_str(&format!("    pub {}: {rust_type},\n", col.name()));
+            table.add_column(col);
+        }
+        model.push_str("}\n");
+        models.push(model);
+        schema.replace_table(table);
+    }
+
+    let mut source = String::from("//! Models generated by `butane introspect`.\n\n");
+    if uses_auto {
+        source.push_str("use butane::{model, AutoPk};\n");
+    } else {
+        source.push_str("use butane::model;\n");
+    }
+    for model in models {
+        source.push('\n');
+        source.push_str(&model);
+    }
+    source
+}
+
+/// The Rust type used for a field of type `ty`, if there is one.
+fn rust_type(ty: &butane::SqlType) -> Option<String> {
+    use butane::SqlType;
+    Some(
+        match ty {
+            SqlType::Bool => "bool",
+            SqlType::Int => "i32",
+            SqlType::BigInt => "i64",
+            SqlType::Real => "f64",
+            SqlType::Text => "String",
+            SqlType::Timestamp => "chrono::NaiveDateTime",
+            SqlType::Date => "chrono::NaiveDate",
+            SqlType::Blob => "Vec<u8>",
+            SqlType::Json => "serde_json::Value",
+            SqlType::Decimal => "rust_decimal::Decimal",
+            SqlType::Custom(_) => return None,
+        }
+        .to_string(),
+    )
+}
+
+/// The name of the struct for table `table_name`, in upper camel case.
+fn struct_name(table_name: &str) -> String {
+    table_name
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Whether `name` can be used as a Rust identifier as is.
+fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ];
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
+
 pub fn unmigrate(base_dir: &PathBuf, name: Option<String>) -> Result<()> {
     let spec = load_connspec(base_dir)?;
     let conn = butane::db::connect(&spec)?;
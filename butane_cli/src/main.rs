@@ -6,8 +6,8 @@
 use butane_cli::{
     add_backend, base_dir, clean, clear_data, collapse_migrations, delete_table,
     describe_migration, detach_latest_migration, embed, get_migrations, handle_error, init,
-    list_backends, list_migrations, make_migration, migrate, regenerate_migrations, remove_backend,
-    unmigrate,
+    introspect, list_backends, list_migrations, make_migration, migrate, regenerate_migrations,
+    remove_backend, unmigrate, verify,
 };
 use clap::{ArgAction, Parser, Subcommand};
 
@@ -57,6 +57,9 @@ enum Commands {
     Migrate {
         /// Migration to migrate to.
         name: Option<String>,
+        /// Print the SQL of the migrations to apply, without applying them.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Regenerate migrations in place.
     Regenerate,
@@ -66,6 +69,14 @@ enum Commands {
     },
     /// List migrations.
     List,
+    /// Generate models and an initial migration from the tables of an existing database.
+    Introspect {
+        /// File to write the models to. If not given, they are printed.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Check that the database schema matches the last applied migration, reporting any differences.
+    Verify,
     /// Replace all migrations with a single migration representing the current model state.
     Collapse {
         /// Name to use for the new migration.
@@ -89,7 +100,7 @@ enum Commands {
         #[clap(subcommand)]
         subcommand: DeleteCommands,
     },
-    /// Clean current migration state. Deletes the current migration working state which is generated on each build. This can be used as a workaround to remove stale tables from the schema, as Butane does not currently auto-detect model removals. The next build will recreate with only tables for the extant models.
+    /// Clean current migration state. Deletes the current migration working state which is generated on each build. The next build will recreate it with only tables for the extant models. Tables of models missing from the build are retained by the next migration and reported by `makemigration`; use `delete table` to drop those whose models were removed.
     Clean,
 }
 
@@ -128,7 +139,7 @@ enum ClearCommands {
 
 #[derive(Subcommand)]
 enum DeleteCommands {
-    /// Clear all data from the database. The schema is left intact, but all instances of all models (i.e. all rows of all tables defined by the models) are deleted.
+    /// Delete a table from the current migration state, e.g. because its model was removed. The next migration will drop the table. Tables are never dropped just because their model is missing from the build.
     Table {
         /// Table name.
         name: String,
@@ -147,19 +158,22 @@ fn main() {
         base_dir.push(".butane");
     }
 
-    // List any detached migrations.
-    if let Ok(ms) = get_migrations(&base_dir) {
-        if let Ok(detached_migrations) = ms.detached_migration_paths() {
-            if !detached_migrations.is_empty() {
-                eprintln!(
-                    "Ignoring detached migrations. Please delete or manually re-attach these:"
-                );
-                for migration in detached_migrations {
-                    eprintln!("- {migration}");
+    // List any detached migrations. There are none before the migrations
+    // directory exists, as when running `init` or `introspect`.
+    if base_dir.join("migrations").is_dir() {
+        if let Ok(ms) = get_migrations(&base_dir) {
+            if let Ok(detached_migrations) = ms.detached_migration_paths() {
+                if !detached_migrations.is_empty() {
+                    eprintln!(
+                        "Ignoring detached migrations. Please delete or manually re-attach these:"
+                    );
+                    for migration in detached_migrations {
+                        eprintln!("- {migration}");
+                    }
                 }
-            }
+            };
         };
-    };
+    }
 
     match &cli.command {
         Commands::Init(args) => handle_error(init(
@@ -177,10 +191,14 @@ fn main() {
         Commands::DescribeMigration { name } => handle_error(describe_migration(&base_dir, name)),
         Commands::Regenerate => handle_error(regenerate_migrations(&base_dir)),
         Commands::DetachMigration => handle_error(detach_latest_migration(&base_dir)),
-        Commands::Migrate { name } => handle_error(migrate(&base_dir, name.to_owned())),
+        Commands::Migrate { name, dry_run } => {
+            handle_error(migrate(&base_dir, name.to_owned(), *dry_run))
+        }
         Commands::Unmigrate { name } => handle_error(unmigrate(&base_dir, name.to_owned())),
         Commands::Embed => handle_error(embed(&base_dir)),
         Commands::List => handle_error(list_migrations(&base_dir)),
+        Commands::Introspect { output } => handle_error(introspect(&base_dir, output.as_deref())),
+        Commands::Verify => handle_error(verify(&base_dir)),
         Commands::Collapse { name } => handle_error(collapse_migrations(&base_dir, Some(name))),
         Commands::Clear { subcommand } => match subcommand {
             ClearCommands::Data => handle_error(clear_data(&base_dir)),
@@ -0,0 +1,70 @@
+#![cfg(feature = "sqlite")]
+
+use butane::db;
+use butane::migrations::{Migration, Migrations};
+
+#[test]
+fn introspect_sqlite() {
+    let dir = std::env::temp_dir().join(format!("butane_introspect_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let db_path = dir.join("db.sqlite");
+    let db_path = db_path.to_str().unwrap();
+    let base_dir = dir.join(".butane");
+
+    let spec = db::ConnectionSpec::new("sqlite", db_path);
+    let conn = db::connect(&spec).unwrap();
+    conn.execute(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR(50) NOT NULL, score REAL, \
+         weird NUMERIC);
+         CREATE TABLE blog_post (slug TEXT NOT NULL PRIMARY KEY, body TEXT NOT NULL);
+         CREATE TABLE log (msg TEXT);",
+    )
+    .unwrap();
+
+    butane_cli::init(&base_dir, "sqlite", db_path, true).unwrap();
+    let models_path = dir.join("models.rs");
+    butane_cli::introspect(&base_dir, Some(&models_path)).unwrap();
+
+    let models = std::fs::read_to_string(&models_path).unwrap();
+    assert_eq!(
+        models,
+        r#"//! Models generated by `butane introspect`.
+
+use butane::{model, AutoPk};
+
+#[model]
+#[table = "blog_post"]
+#[derive(Debug, Default)]
+pub struct BlogPost {
+    #[pk]
+    pub slug: String,
+    pub body: String,
+}
+
+// Table `log` was omitted, as it has no primary key or its name is not supported.
+
+#[model]
+#[table = "users"]
+#[derive(Debug, Default)]
+pub struct Users {
+    pub id: AutoPk<i64>,
+    pub name: String,
+    pub score: Option<f64>,
+    // Column `weird` was omitted, as its name or type is not supported.
+}
+"#
+    );
+
+    // The initial migration matches the models and is already applied.
+    let ms = butane_cli::get_migrations(&base_dir).unwrap();
+    assert!(ms.unapplied_migrations(&conn).unwrap().is_empty());
+    let schema = ms.latest().unwrap().db().unwrap();
+    let tables: Vec<&str> = schema.tables().map(|t| t.name.as_str()).collect();
+    assert_eq!(tables, ["blog_post", "users"]);
+    let users = schema.get_table("users").unwrap();
+    let columns: Vec<&str> = users.columns.iter().map(|c| c.name()).collect();
+    assert_eq!(columns, ["id", "name", "score"]);
+    assert!(users.pk().unwrap().is_auto());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -33,6 +33,13 @@
 ///    (perhaps implemented as the SQL UNIQUE constraint by some backends).
 /// * `#[default]` should be used on fields added by later migrations to avoid errors on existing objects.
 ///     Unnecessary if the new field is an `Option<>`
+/// * `#[index]` on a field creates a secondary index on that field's column.
+/// * `#[index(fields = ["a", "b"])]` used on the struct creates a secondary index over
+///   several columns. An index name may be given with `name = "NAME"`, otherwise one is
+///   derived from the table and column names.
+/// * `#[check = "EXPR"]` on a field or on the struct adds a CHECK constraint, e.g.
+///   `#[check = "likes >= 0"]`. The expression is passed through verbatim, so it is
+///   backend-specific SQL and must be valid for every backend the migrations target.
 ///
 /// For example
 /// ```ignore
@@ -288,6 +295,9 @@ fn to_sql(&self) -> butane::SqlVal {
             fn to_sql_ref(&self) -> butane::SqlValRef<'_> {
                 self.0.to_sql_ref()
             }
+            fn try_to_sql_ref(&self) -> butane::Result<butane::SqlValRef<'_>> {
+                self.0.try_to_sql_ref()
+            }
         }
         impl butane::FromSql for #ident
         {
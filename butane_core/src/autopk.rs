@@ -64,6 +64,9 @@ fn to_sql(&self) -> SqlVal {
     fn to_sql_ref(&self) -> SqlValRef<'_> {
         self.expect_inner().to_sql_ref()
     }
+    fn try_to_sql_ref(&self) -> Result<SqlValRef<'_>> {
+        self.expect_inner().try_to_sql_ref()
+    }
     fn into_sql(self) -> SqlVal {
         self.inner.expect("PK is not generated yet!").into_sql()
     }
@@ -48,13 +48,13 @@ pub fn impl_dbobject(ast_struct: &ItemStruct, config: &Config) -> TokenStream2 {
 
     let non_auto_values_fn = if values.is_empty() {
         quote!(
-            fn non_auto_values(&self, _include_pk: bool) -> Vec<butane::SqlValRef> {
-                return vec![];
+            fn non_auto_values(&self, _include_pk: bool) -> butane::Result<Vec<butane::SqlValRef>> {
+                return Ok(vec![]);
             }
         )
     } else {
         quote!(
-            fn non_auto_values(&self, include_pk: bool) -> Vec<butane::SqlValRef> {
+            fn non_auto_values(&self, include_pk: bool) -> butane::Result<Vec<butane::SqlValRef>> {
                 let mut values: Vec<butane::SqlValRef> = Vec::with_capacity(
                     <Self as butane::DataResult>::COLUMNS.len()
                 );
@@ -63,7 +63,7 @@ fn non_auto_values(&self, include_pk: bool) -> Vec<butane::SqlValRef> {
                 } else {
                     #(#values_no_pk)*
                 }
-                values
+                Ok(values)
             }
         )
     };
@@ -421,7 +421,7 @@ fn push_values<P>(ast_struct: &ItemStruct, mut predicate: P) -> Vec<TokenStream2
         .filter(|f| is_row_field(f) && !is_auto(f) && predicate(f))
         .map(|f| {
             let ident = f.ident.clone().unwrap();
-            quote!(values.push(butane::ToSql::to_sql_ref(&self.#ident));)
+            quote!(values.push(butane::ToSql::try_to_sql_ref(&self.#ident)?);)
         })
         .collect()
 }
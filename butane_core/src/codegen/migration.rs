@@ -1,10 +1,13 @@
 use syn::{Field, ItemStruct};
 
 use super::{
-    dbobj, fields, get_default, get_deferred_sql_type, get_many_sql_type, is_auto, is_foreign_key,
-    is_many_to_many, is_option, is_row_field, is_unique, pk_field,
+    dbobj, fields, get_checks, get_default, get_deferred_sql_type, get_field_check,
+    get_many_sql_type, get_struct_indexes, is_auto, is_foreign_key, is_indexed, is_many_to_many,
+    is_option, is_row_field, is_unique, pk_field,
+};
+use crate::migrations::adb::{
+    create_many_table, AColumn, AIndex, ARef, ATable, DeferredSqlType, TypeKey,
 };
-use crate::migrations::adb::{create_many_table, AColumn, ARef, ATable, DeferredSqlType, TypeKey};
 use crate::migrations::{MigrationMut, MigrationsMut};
 use crate::Result;
 
@@ -61,11 +64,20 @@ fn create_atables(ast_struct: &ItemStruct, config: &dbobj::Config) -> Vec<ATable
             if is_foreign_key(f) {
                 col.add_reference(&ARef::Deferred(deferred_type))
             }
+            col.set_check(get_field_check(f).expect("Malformed check attribute"));
+            if is_indexed(f) {
+                let index = AIndex::new_default_name(&table.name, vec![col.name().to_string()]);
+                table.add_index(index);
+            }
             table.add_column(col);
         } else if is_many_to_many(f) {
             result.push(many_table(&table.name, f, &pk));
         }
     }
+    for index in get_struct_indexes(ast_struct, &table.name).expect("Malformed index attribute") {
+        table.add_index(index);
+    }
+    table.checks = get_checks(&ast_struct.attrs).expect("Malformed check attribute");
     result.insert(0, table);
     result
 }
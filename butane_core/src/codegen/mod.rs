@@ -10,7 +10,7 @@
     MetaNameValue,
 };
 
-use crate::migrations::adb::{DeferredSqlType, TypeIdentifier, TypeKey};
+use crate::migrations::adb::{AIndex, DeferredSqlType, TypeIdentifier, TypeKey};
 use crate::migrations::{MigrationMut, MigrationsMut};
 use crate::{SqlType, SqlVal};
 
@@ -209,7 +209,11 @@ fn filter_helper_attributes(ast_struct: &ItemStruct) -> Vec<Attribute> {
         .attrs
         .clone()
         .into_iter()
-        .filter(|a| !a.path().is_ident("table"))
+        .filter(|a| {
+            !a.path().is_ident("table")
+                && !a.path().is_ident("index")
+                && !a.path().is_ident("check")
+        })
         .collect()
 }
 
@@ -244,6 +248,8 @@ fn remove_helper_field_attributes(
                         && !a.path().is_ident("sqltype")
                         && !a.path().is_ident("default")
                         && !a.path().is_ident("unique")
+                        && !a.path().is_ident("index")
+                        && !a.path().is_ident("check")
                 });
             }
             Ok(fields)
@@ -276,6 +282,93 @@ fn is_unique(field: &Field) -> bool {
         .any(|attr| attr.path().is_ident("unique"))
 }
 
+fn is_indexed(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path().is_ident("index"))
+}
+
+/// Get the indexes declared on the struct with
+/// `#[index(name = "...", fields = ["a", "b"])]`. Unnamed indexes are
+/// given a default name derived from `table_name`.
+fn get_struct_indexes(
+    ast_struct: &ItemStruct,
+    table_name: &str,
+) -> std::result::Result<Vec<AIndex>, CompilerErrorMsg> {
+    let mut indexes = Vec::new();
+    for attr in ast_struct
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("index"))
+    {
+        let mut name: Option<String> = None;
+        let mut columns: Vec<String> = Vec::new();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("fields") {
+                let array: syn::ExprArray = meta.value()?.parse()?;
+                for elem in array.elems {
+                    match elem {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) => columns.push(s.value()),
+                        _ => return Err(meta.error("index fields must be string literals")),
+                    }
+                }
+                Ok(())
+            } else {
+                Err(meta.error("unsupported index property"))
+            }
+        })
+        .map_err(|e| make_compile_error!("malformed index attribute: {}", e))?;
+        if columns.is_empty() {
+            return Err(
+                make_compile_error!("index attribute must specify at least one field").into(),
+            );
+        }
+        for column in &columns {
+            if !fields(ast_struct).any(|f| f.ident.as_ref().is_some_and(|i| i == column)) {
+                return Err(make_compile_error!("index refers to unknown field {}", column).into());
+            }
+        }
+        indexes.push(match name {
+            Some(name) => AIndex::new(name, columns),
+            None => AIndex::new_default_name(table_name, columns),
+        });
+    }
+    Ok(indexes)
+}
+
+/// Get the CHECK constraint expressions from `#[check = "..."]` attributes.
+fn get_checks(attrs: &[Attribute]) -> std::result::Result<Vec<String>, CompilerErrorMsg> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("check"))
+        .map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(s), ..
+                    }),
+                ..
+            }) => Ok(s.value()),
+            _ => Err(
+                make_compile_error!("malformed check attribute, expected #[check = \"...\"]")
+                    .into(),
+            ),
+        })
+        .collect()
+}
+
+/// Get the CHECK constraint expression for a field, if any.
+fn get_field_check(field: &Field) -> std::result::Result<Option<String>, CompilerErrorMsg> {
+    let mut checks = get_checks(&field.attrs)?;
+    if checks.len() > 1 {
+        return Err(make_compile_error!("only one check attribute is allowed per field").into());
+    }
+    Ok(checks.pop())
+}
+
 fn fields(ast_struct: &ItemStruct) -> impl Iterator<Item = &Field> {
     ast_struct.fields.iter()
 }
@@ -453,9 +546,10 @@ pub fn get_primitive_sql_type(ty: &syn::Type) -> Option<DeferredSqlType> {
         || *ty == parse_quote!(i32)
     {
         return some_known(SqlType::Int);
-    } else if *ty == parse_quote!(u32) || *ty == parse_quote!(i64) {
+    } else if *ty == parse_quote!(u32) || *ty == parse_quote!(i64) || *ty == parse_quote!(u64) {
         // Future improvement: better support unsigned integers
-        // here. Sqlite has no u64, though Postgres does
+        // here. Neither Sqlite nor Postgres has a u64, so values
+        // above i64::MAX are rejected when saving.
         return some_known(SqlType::BigInt);
     } else if *ty == parse_quote!(f32) || *ty == parse_quote!(f64) {
         return some_known(SqlType::Real);
@@ -472,6 +566,13 @@ pub fn get_primitive_sql_type(ty: &syn::Type) -> Option<DeferredSqlType> {
         }
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        if *ty == parse_quote!(rust_decimal::Decimal) || *ty == parse_quote!(Decimal) {
+            return some_known(SqlType::Decimal);
+        }
+    }
+
     #[cfg(feature = "datetime")]
     {
         // Note, the fact that we have to check specific paths because
@@ -482,6 +583,7 @@ pub fn get_primitive_sql_type(ty: &syn::Type) -> Option<DeferredSqlType> {
         if let Some(syn::PathSegment { ident, arguments }) = last_path_segment(ty) {
             match ident.to_string().as_str() {
                 "NaiveDateTime" => return some_known(SqlType::Timestamp),
+                "NaiveDate" => return some_known(SqlType::Date),
                 "DateTime" => {
                     // Only if the parameter is UTC, as we don't support attached
                     // time zones
@@ -498,6 +600,18 @@ pub fn get_primitive_sql_type(ty: &syn::Type) -> Option<DeferredSqlType> {
         }
     }
 
+    #[cfg(feature = "time")]
+    {
+        // As above, we expect these to come from the time crate.
+        if let Some(syn::PathSegment { ident, .. }) = last_path_segment(ty) {
+            match ident.to_string().as_str() {
+                "OffsetDateTime" | "PrimitiveDateTime" => return some_known(SqlType::Timestamp),
+                "Date" => return some_known(SqlType::Date),
+                _ => {}
+            }
+        }
+    }
+
     #[cfg(feature = "uuid")]
     {
         if *ty == parse_quote!(Uuid) || *ty == parse_quote!(uuid::Uuid) {
@@ -582,8 +696,12 @@ fn sqltype_from_name(name: &Ident) -> Option<TypeIdentifier> {
         "Blob" => return some_id(SqlType::Blob),
         #[cfg(feature = "json")]
         "Json" => return some_id(SqlType::Json),
+        #[cfg(feature = "decimal")]
+        "Decimal" => return some_id(SqlType::Decimal),
         #[cfg(feature = "datetime")]
         "Timestamp" => return some_id(SqlType::Timestamp),
+        #[cfg(feature = "datetime")]
+        "Date" => return some_id(SqlType::Date),
         _ => (),
     }
     if let Some(custom_name) = Regex::new(r"^Custom\((.*)\)$").unwrap().captures(&name) {
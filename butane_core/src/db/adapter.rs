@@ -117,6 +117,29 @@ fn invoke_blocking<'c, 's, 'result, F, T, U>(&'s self, context: *const T, func:
         rx.recv()?
     }
 
+    fn invoke_mut_blocking<'c, 's, 'result, F, T, U>(
+        &'s self,
+        context: *mut T,
+        func: F,
+    ) -> Result<U>
+    where
+        F: FnOnce(&'c mut T) -> Result<U> + Send,
+        F: 'result,
+        U: Send + 'result,
+        T: ?Sized + 'c,
+        's: 'result,
+        'c: 'result,
+    {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let context_ptr = unsafe { SyncSendPtrMut::new(context) };
+        let func_taking_ptr = |ctx: SyncSendPtrMut<T>| func(unsafe { ctx.inner.as_mut() }.unwrap());
+        unsafe {
+            let wrapped_func = move || _ = tx.send(func_taking_ptr(context_ptr));
+            self.invoke_internal_unsafe(wrapped_func)?;
+        }
+        rx.recv()?
+    }
+
     unsafe fn invoke_internal_unsafe<'s, 'result>(
         &'s self,
         // wrapped_func is a complete encapsulation of the function we
@@ -245,6 +268,16 @@ fn invoke_blocking<'c, 'result, F, U>(&'c self, func: F) -> Result<U>
     {
         self.env.invoke_blocking(self.context.inner, func)
     }
+
+    fn invoke_mut_blocking<'c, 'result, F, U>(&'c mut self, func: F) -> Result<U>
+    where
+        F: FnOnce(&'c mut T) -> Result<U> + Send,
+        F: 'result,
+        U: Send + 'result,
+        'c: 'result,
+    {
+        self.env.invoke_mut_blocking(self.context.inner, func)
+    }
 }
 
 impl<T> AsyncAdapter<T> {
@@ -362,6 +395,12 @@ async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     async fn has_table(&self, table: &str) -> Result<bool> {
         self.invoke(|conn| conn.has_table(table)).await
     }
+    async fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+        self.invoke(|conn| conn.introspect_table(table)).await
+    }
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        self.invoke(|conn| conn.list_tables()).await
+    }
 }
 
 #[async_trait]
@@ -394,6 +433,18 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         ok_or_panic_with_adapter_error(self.invoke_blocking(|conn| Ok(conn.is_closed())))
     }
+
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        ok_or_panic_with_adapter_error(self.invoke_mut_blocking(|conn| {
+            conn.set_sql_comment(comment);
+            Ok(())
+        }))
+    }
+
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.invoke_mut(move |conn| conn.set_statement_timeout(timeout))
+            .await
+    }
 }
 
 fn ok_or_panic_with_adapter_error<T>(r: Result<T>) -> T {
@@ -437,6 +488,12 @@ async fn commit(&mut self) -> Result<()> {
     async fn rollback(&mut self) -> Result<()> {
         self.invoke_mut(|conn| conn.rollback()).await
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        ok_or_panic_with_adapter_error(self.invoke_mut_blocking(|conn| {
+            conn.set_sql_comment(comment);
+            Ok(())
+        }))
+    }
     fn connection_methods(&self) -> &dyn ConnectionMethodsAsync {
         self
     }
@@ -0,0 +1,81 @@
+//! Comments used to tag generated SQL statements with metadata.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+/// Metadata rendered as a SQL comment and prepended to the statements
+/// butane generates, allowing queries seen in database logs to be
+/// correlated with their source (in the style of
+/// [sqlcommenter](https://google.github.io/sqlcommenter/)).
+///
+/// Set on a connection with
+/// [`set_sql_comment`][crate::db::BackendConnection::set_sql_comment]. Transactions
+/// begun from that connection inherit it.
+///
+/// Tags render as `/*key='value',...*/`, sorted by key. Keys and values are
+/// percent-encoded so that the comment can never terminate early or otherwise
+/// alter the statement it is attached to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SqlComment {
+    tags: BTreeMap<String, String>,
+}
+
+impl SqlComment {
+    /// Create an empty comment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add a tag, replacing any existing tag with the same key.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_tag(key, value);
+        self
+    }
+    /// Add a tag, replacing any existing tag with the same key.
+    pub fn set_tag(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.tags.insert(key.into(), value.into());
+    }
+    /// Remove the tag with the given key, returning its value if it was present.
+    pub fn remove_tag(&mut self, key: &str) -> Option<String> {
+        self.tags.remove(key)
+    }
+    /// Get the value of the tag with the given key.
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(|v| v.as_str())
+    }
+    /// Returns true if there are no tags. An empty comment is not rendered.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+impl fmt::Display for SqlComment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        f.write_str("/*")?;
+        for (i, (key, value)) in self.tags.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write_encoded(key, f)?;
+            f.write_str("='")?;
+            write_encoded(value, f)?;
+            f.write_char('\'')?;
+        }
+        f.write_str("*/")
+    }
+}
+
+/// Percent-encodes everything other than RFC 3986 unreserved characters.
+fn write_encoded(s: &str, w: &mut impl Write) -> fmt::Result {
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                w.write_char(b as char)?
+            }
+            _ => write!(w, "%{b:02X}")?,
+        }
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 
+use crate::migrations::adb::ATable;
 use crate::query::{BoolExpr, Expr, Order};
 use crate::{Result, SqlType, SqlVal, SqlValRef};
 
@@ -67,6 +68,13 @@ async fn delete(&self, table: &str, pkcol: &'static str, pk: SqlVal) -> Result<(
     async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize>;
     /// Tests if a table exists in the database.
     async fn has_table(&self, table: &str) -> Result<bool>;
+    /// Describe the columns of a table as found in the database, or
+    /// `None` if the table does not exist. Column types are reported
+    /// using the backend's own type names, as
+    /// [`TypeIdentifier::Name`][crate::migrations::adb::TypeIdentifier::Name].
+    async fn introspect_table(&self, table: &str) -> Result<Option<ATable>>;
+    /// Names of all the tables in the database.
+    async fn list_tables(&self) -> Result<Vec<String>>;
 }
 
 /// Represents a database column. Most users do not need to use this
@@ -22,6 +22,12 @@ fn name(&self) -> &'static str {
     fn create_migration_sql(&self, current: &adb::ADB, ops: Vec<adb::Operation>) -> Result<String> {
         Err(Error::PoisonedConnection)
     }
+    fn column_type_name(&self, col: &adb::AColumn) -> Result<String> {
+        Err(Error::PoisonedConnection)
+    }
+    fn read_schema(&self, conn: &dyn ConnectionMethods) -> Result<adb::ADB> {
+        Err(Error::PoisonedConnection)
+    }
     fn connect(&self, conn_str: &str) -> Result<Connection> {
         Err(Error::PoisonedConnection)
     }
@@ -107,6 +113,12 @@ async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     async fn has_table(&self, table: &str) -> Result<bool> {
         Err(Error::PoisonedConnection)
     }
+    async fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+        Err(Error::PoisonedConnection)
+    }
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        Err(Error::PoisonedConnection)
+    }
 }
 
 #[maybe_async_cfg::maybe(
@@ -132,4 +144,8 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         true
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {}
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        Err(Error::PoisonedConnection)
+    }
 }
@@ -6,10 +6,11 @@
 use std::borrow::Cow;
 use std::fmt::Write;
 
-use super::Column;
-use crate::migrations::adb::{AColumn, TypeIdentifier};
+use super::{Column, ConnectionMethods, SqlComment};
+use crate::migrations::adb::{AColumn, AIndex, DeferredSqlType, TypeIdentifier, ADB};
+use crate::migrations::migrations_table;
 use crate::query::Expr::{Condition, Placeholder, Val};
-use crate::query::{BoolExpr::*, Expr, Join, Order, OrderDirection};
+use crate::query::{BoolExpr::*, Expr, Join, Order, OrderDirection, OrderExpr};
 use crate::Error;
 use crate::{query, Result, SqlType, SqlVal};
 
@@ -17,6 +18,13 @@ pub trait PlaceholderSource {
     fn next_placeholder(&mut self) -> Cow<str>;
 }
 
+/// Writes `comment`, if present and non-empty, followed by a space so that it prefixes the statement to come.
+pub fn sql_comment(comment: Option<&SqlComment>, w: &mut impl Write) {
+    if let Some(comment) = comment.filter(|c| !c.is_empty()) {
+        write!(w, "{comment} ").unwrap();
+    }
+}
+
 /// Quotes the `word` if it is a reserved word.
 pub fn quote_reserved_word(word: &str) -> Cow<str> {
     if sqlparser::keywords::ALL_KEYWORDS.contains(&word.to_uppercase().as_str()) {
@@ -205,7 +213,7 @@ pub fn sql_offset(offset: i32, w: &mut impl Write) {
     write!(w, " OFFSET {offset}").unwrap();
 }
 
-/// Writes to `w` the SQL of the list of column `order`.
+/// Writes to `w` the SQL of the list of `order` terms.
 pub fn sql_order(order: &[Order], w: &mut impl Write) {
     write!(w, " ORDER BY ").unwrap();
     order.iter().fold("", |sep, o| {
@@ -213,11 +221,34 @@ pub fn sql_order(order: &[Order], w: &mut impl Write) {
             OrderDirection::Ascending => "ASC",
             OrderDirection::Descending => "DESC",
         };
-        write!(w, "{}{} {}", sep, quote_reserved_word(o.column), sql_dir).unwrap();
+        w.write_str(sep).unwrap();
+        sql_order_expr(&o.expr, w);
+        write!(w, " {sql_dir}").unwrap();
         ", "
     });
 }
 
+fn sql_order_expr(expr: &OrderExpr, w: &mut impl Write) {
+    match expr {
+        OrderExpr::Column(column) => w.write_str(&quote_reserved_word(column)),
+        OrderExpr::ManyCount {
+            many_table,
+            owner_pk,
+        } => {
+            // (SELECT COUNT(*) FROM <many_table> WHERE <many_table>.owner = <owner_pk>)
+            let many_table = quote_reserved_word(many_table);
+            write!(
+                w,
+                "(SELECT COUNT(*) FROM {many_table} WHERE {many_table}.owner = "
+            )
+            .unwrap();
+            sql_column(owner_pk.clone(), w);
+            w.write_str(")")
+        }
+    }
+    .unwrap()
+}
+
 /// Return column default.
 pub fn column_default(col: &AColumn) -> Result<SqlVal> {
     if let Some(val) = col.default() {
@@ -236,16 +267,92 @@ pub fn column_default(col: &AColumn) -> Result<SqlVal> {
             SqlType::Blob => SqlVal::Blob(Vec::new()),
             #[cfg(feature = "json")]
             SqlType::Json => SqlVal::Json(serde_json::Value::default()),
+            #[cfg(feature = "decimal")]
+            SqlType::Decimal => SqlVal::Decimal(rust_decimal::Decimal::ZERO),
             #[cfg(feature = "datetime")]
             SqlType::Timestamp => {
                 SqlVal::Timestamp(chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc())
             }
+            #[cfg(feature = "datetime")]
+            SqlType::Date => {
+                SqlVal::Date(chrono::DateTime::from_timestamp(0, 0).unwrap().date_naive())
+            }
             SqlType::Custom(_) => return Err(Error::NoCustomDefault),
         },
         TypeIdentifier::Name(_) => return Err(Error::NoCustomDefault),
     })
 }
 
+/// Return the SQL for a CHECK constraint clause on `expr`.
+///
+/// The expression is backend-specific SQL and is passed through verbatim.
+/// Reads the schema of every table in the database other than the
+/// migrations table, using `sqltype` to find the [`SqlType`] for each
+/// type name the backend reports.
+pub fn read_schema(
+    conn: &dyn ConnectionMethods,
+    sqltype: impl Fn(&str) -> Option<SqlType>,
+) -> Result<ADB> {
+    let migrations_table = migrations_table().name;
+    let mut db = ADB::new();
+    for name in conn.list_tables()? {
+        if name == migrations_table {
+            continue;
+        }
+        let Some(mut table) = conn.introspect_table(&name)? else {
+            continue;
+        };
+        for col in std::mem::take(&mut table.columns) {
+            let ty = match col.typeid()? {
+                TypeIdentifier::Name(name) => match sqltype(&name) {
+                    Some(ty) => TypeIdentifier::Ty(ty),
+                    None => TypeIdentifier::Name(name),
+                },
+                ty => ty,
+            };
+            // Some backends, like sqlite, permit nulls in primary keys
+            // not declared NOT NULL, but butane does not.
+            table.add_column(AColumn::new(
+                col.name(),
+                DeferredSqlType::KnownId(ty),
+                col.nullable() && !col.is_pk(),
+                col.is_pk(),
+                col.is_auto(),
+                col.unique(),
+                col.default().clone(),
+                col.reference().clone(),
+            ));
+        }
+        db.replace_table(table);
+    }
+    Ok(db)
+}
+
+pub fn check_constraint(expr: &str) -> String {
+    format!("CHECK ({expr})")
+}
+
+/// Return the SQL to create `index` on the table `tbl_name`.
+pub fn create_index(tbl_name: &str, index: &AIndex) -> String {
+    let columns = index
+        .columns
+        .iter()
+        .map(|c| quote_reserved_word(c))
+        .collect::<Vec<Cow<str>>>()
+        .join(", ");
+    format!(
+        "CREATE INDEX {} ON {} ({});",
+        quote_reserved_word(&index.name),
+        quote_reserved_word(tbl_name),
+        columns
+    )
+}
+
+/// Return the SQL to drop the index named `name`.
+pub fn drop_index(name: &str) -> String {
+    format!("DROP INDEX {};", quote_reserved_word(name))
+}
+
 /// Writes to `w` the SQL of the list of `columns`.
 pub fn list_columns(columns: &[Column], w: &mut impl Write) {
     let mut colnames: Vec<&'static str> = Vec::new();
@@ -305,8 +412,12 @@ pub fn sql_literal_value(val: &SqlVal) -> Result<String> {
         Blob(val) => Ok(format!("x'{}'", hex::encode_upper(val))),
         #[cfg(feature = "json")]
         Json(val) => Ok(format!("{val}")),
+        #[cfg(feature = "decimal")]
+        Decimal(val) => Ok(format!("'{val}'")),
         #[cfg(feature = "datetime")]
         Timestamp(ndt) => Ok(ndt.format("'%Y-%m-%dT%H:%M:%S%.f'").to_string()),
+        #[cfg(feature = "datetime")]
+        Date(nd) => Ok(nd.format("'%Y-%m-%d'").to_string()),
         Custom(val) => Err(Error::LiteralForCustomUnsupported(*(*val).clone())),
     }
 }
@@ -85,6 +85,14 @@ async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
             async fn has_table(&self, table: &str) -> Result<bool> {
                 self.wrapped_connection_methods()?.has_table(table).await
             }
+            async fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+                self.wrapped_connection_methods()?
+                    .introspect_table(table)
+                    .await
+            }
+            async fn list_tables(&self) -> Result<Vec<String>> {
+                self.wrapped_connection_methods()?.list_tables().await
+            }
         }
     };
 }
@@ -20,6 +20,7 @@
 use std::io::Write;
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
@@ -40,6 +41,8 @@
 #[cfg(feature = "async")]
 pub use sync_adapter::SyncAdapter;
 
+mod comment;
+pub use comment::SqlComment;
 mod connmethods;
 #[cfg(feature = "async")]
 pub use connmethods::ConnectionMethodsAsync;
@@ -48,6 +51,8 @@
 };
 mod helper;
 mod macros;
+#[cfg(feature = "mysql")]
+pub mod mysql;
 #[cfg(feature = "pg")]
 pub mod pg;
 
@@ -103,6 +108,15 @@ pub trait BackendConnection: ConnectionMethods + Debug + Send {
     /// Tests if the connection has been closed. Backends which do not
     /// support this check should return false.
     fn is_closed(&self) -> bool;
+    /// Set the comment prepended to statements generated by this
+    /// connection, or clear it with `None`. Transactions begun from
+    /// this connection inherit it.
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>);
+    /// Limit how long each statement run on this connection, or on
+    /// transactions begun from it, may take, or remove the limit with
+    /// `None`. A statement exceeding the limit fails with
+    /// [`Error::Timeout`][crate::Error::Timeout].
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()>;
 }
 
 #[maybe_async_cfg::maybe(
@@ -129,6 +143,12 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         self.deref().is_closed()
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.deref_mut().set_sql_comment(comment)
+    }
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.deref_mut().set_statement_timeout(timeout).await
+    }
 }
 
 #[maybe_async_cfg::maybe(
@@ -204,6 +224,12 @@ async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     async fn has_table(&self, table: &str) -> Result<bool> {
         self.deref().has_table(table).await
     }
+    async fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+        self.deref().introspect_table(table).await
+    }
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        self.deref().list_tables().await
+    }
 }
 
 /// Database connection. May be a connection to any type of database
@@ -311,6 +337,12 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         self.conn.is_closed()
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.conn.set_sql_comment(comment)
+    }
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.conn.set_statement_timeout(timeout).await
+    }
 }
 connection_method_wrapper!(Connection);
 
@@ -331,6 +363,8 @@ pub(super) trait BackendTransaction<'c>:
     async fn commit(&mut self) -> Result<()>;
     /// Roll back the transaction. Same comment about consuming self as above.
     async fn rollback(&mut self) -> Result<()>;
+    /// Set the comment prepended to statements generated within this transaction.
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>);
 
     // Workaround for https://github.com/rust-lang/rfcs/issues/2765
     fn connection_methods(&self) -> &dyn ConnectionMethods;
@@ -372,6 +406,12 @@ pub async fn commit(mut self) -> Result<()> {
     pub async fn rollback(mut self) -> Result<()> {
         self.trans.deref_mut().rollback().await
     }
+    /// Set the comment prepended to statements generated within this
+    /// transaction, or clear it with `None`. By default a transaction
+    /// uses the comment of the connection it was begun from.
+    pub fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.trans.set_sql_comment(comment)
+    }
     // For use with connection_method_wrapper macro.
     #[allow(clippy::unnecessary_wraps)]
     fn wrapped_connection_methods(&self) -> Result<&dyn ConnectionMethods> {
@@ -398,6 +438,9 @@ async fn commit(&mut self) -> Result<()> {
     async fn rollback(&mut self) -> Result<()> {
         self.trans.deref_mut().rollback().await
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.trans.set_sql_comment(comment)
+    }
     fn connection_methods(&self) -> &dyn ConnectionMethods {
         self
     }
@@ -420,6 +463,9 @@ async fn commit(&mut self) -> Result<()> {
     async fn rollback(&mut self) -> Result<()> {
         self.deref_mut().rollback().await
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.deref_mut().set_sql_comment(comment)
+    }
     fn connection_methods(&self) -> &dyn ConnectionMethods {
         self
     }
@@ -498,6 +544,12 @@ async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     async fn has_table(&self, table: &str) -> Result<bool> {
         self.deref().has_table(table).await
     }
+    async fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+        self.deref().introspect_table(table).await
+    }
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        self.deref().list_tables().await
+    }
 }
 
 /// Database backend. A boxed implementation can be returned by name via [get_backend][crate::db::get_backend].
@@ -505,6 +557,16 @@ async fn has_table(&self, table: &str) -> Result<bool> {
 pub trait Backend: Send + Sync + DynClone {
     fn name(&self) -> &'static str;
     fn create_migration_sql(&self, current: &adb::ADB, ops: Vec<adb::Operation>) -> Result<String>;
+    /// The name of the type this backend uses for column `col`, in
+    /// the form reported by [`ConnectionMethods::introspect_table`].
+    fn column_type_name(&self, col: &adb::AColumn) -> Result<String>;
+    /// Read the schema of all tables in the database `conn` is
+    /// connected to, other than butane's own migrations table. This is
+    /// best-effort: columns whose type has no [`SqlType`] equivalent
+    /// keep the backend's type name, as
+    /// [`TypeIdentifier::Name`][adb::TypeIdentifier::Name], and
+    /// constraints other than primary keys are not read.
+    fn read_schema(&self, conn: &dyn ConnectionMethods) -> Result<adb::ADB>;
     /// Establish a new sync connection. The format of the connection
     /// string is backend-dependent.
     fn connect(&self, conn_str: &str) -> Result<Connection>;
@@ -568,6 +630,12 @@ fn name(&self) -> &'static str {
     fn create_migration_sql(&self, current: &adb::ADB, ops: Vec<adb::Operation>) -> Result<String> {
         self.deref().create_migration_sql(current, ops)
     }
+    fn column_type_name(&self, col: &adb::AColumn) -> Result<String> {
+        self.deref().column_type_name(col)
+    }
+    fn read_schema(&self, conn: &dyn ConnectionMethods) -> Result<adb::ADB> {
+        self.deref().read_schema(conn)
+    }
     fn connect(&self, conn_str: &str) -> Result<Connection> {
         self.deref().connect(conn_str)
     }
@@ -584,6 +652,8 @@ pub fn get_backend(name: &str) -> Option<Box<dyn Backend>> {
         sqlite::BACKEND_NAME => Some(Box::new(sqlite::SQLiteBackend::new())),
         #[cfg(feature = "pg")]
         pg::BACKEND_NAME => Some(Box::new(pg::PgBackend::new())),
+        #[cfg(feature = "mysql")]
+        mysql::BACKEND_NAME => Some(Box::new(mysql::MysqlBackend::new())),
         _ => None,
     }
 }
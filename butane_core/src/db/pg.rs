@@ -1,11 +1,12 @@
 //! Postgresql database backend
 use std::borrow::Cow;
 use std::fmt::{Debug, Write};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::BufMut;
 #[cfg(feature = "datetime")]
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use futures_util::stream::StreamExt;
 use tokio_postgres as postgres;
 use tokio_postgres::GenericClient;
@@ -16,10 +17,12 @@
 use crate::db::{
     Backend, BackendConnectionAsync as BackendConnection, BackendRow,
     BackendTransactionAsync as BackendTransaction, Column, Connection, ConnectionAsync,
-    ConnectionMethodsAsync as ConnectionMethods, RawQueryResult, SyncAdapter,
+    ConnectionMethodsAsync as ConnectionMethods, RawQueryResult, SqlComment, SyncAdapter,
     TransactionAsync as Transaction,
 };
-use crate::migrations::adb::{AColumn, ARef, ATable, Operation, TypeIdentifier, ADB};
+use crate::migrations::adb::{
+    AColumn, ARef, ATable, DeferredSqlType, Operation, TypeIdentifier, ADB,
+};
 use crate::query::{BoolExpr, Expr};
 use crate::{debug, query, warn, Error, Result, SqlType, SqlVal, SqlValRef};
 
@@ -51,6 +54,14 @@ fn create_migration_sql(&self, current: &ADB, ops: Vec<Operation>) -> Result<Str
         Ok(lines.join("\n"))
     }
 
+    fn column_type_name(&self, col: &AColumn) -> Result<String> {
+        Ok(col_sqltype(col)?.to_uppercase())
+    }
+
+    fn read_schema(&self, conn: &dyn crate::db::ConnectionMethods) -> Result<ADB> {
+        helper::read_schema(conn, sqltype_from_name)
+    }
+
     fn connect(&self, path: &str) -> Result<Connection> {
         debug!("Postgres connecting via sync adapter");
         let conn = SyncAdapter::new(self.clone())?.connect(path)?;
@@ -69,6 +80,7 @@ pub struct PgConnection {
     #[cfg(feature = "debug")]
     params: Box<str>,
     client: postgres::Client,
+    comment: Option<SqlComment>,
 }
 
 impl PgConnection {
@@ -78,6 +90,7 @@ async fn open(params: &str) -> Result<Self> {
             #[cfg(feature = "debug")]
             params: params.into(),
             client,
+            comment: None,
         })
     }
     async fn connect(params: &str) -> Result<postgres::Client> {
@@ -104,13 +117,16 @@ impl PgConnectionLike for PgConnection {
     fn client(&self) -> Result<&Self::Client> {
         Ok(&self.client)
     }
+    fn sql_comment(&self) -> Option<&SqlComment> {
+        self.comment.as_ref()
+    }
 }
 
 #[async_trait]
 impl BackendConnection for PgConnection {
     async fn transaction(&mut self) -> Result<Transaction<'_>> {
         let trans: postgres::Transaction<'_> = self.client.transaction().await?;
-        let trans = Box::new(PgTransaction::new(trans));
+        let trans = Box::new(PgTransaction::new(trans, self.comment.clone()));
         Ok(Transaction::new(trans))
     }
     fn backend(&self) -> Box<dyn Backend> {
@@ -122,6 +138,18 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         self.client.is_closed()
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.comment = comment;
+    }
+    async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let sql = match timeout {
+            // Zero disables the limit, so round up.
+            Some(timeout) => format!("SET statement_timeout = {};", timeout.as_millis().max(1)),
+            None => "RESET statement_timeout;".to_string(),
+        };
+        self.client.batch_execute(&sql).await?;
+        Ok(())
+    }
 }
 impl Debug for PgConnection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -149,6 +177,8 @@ fn sqlvalref_for_pg_query<'a>(v: &'a SqlValRef<'a>) -> &'a dyn postgres::types::
 trait PgConnectionLike {
     type Client: postgres::GenericClient + Send;
     fn client(&self) -> Result<&Self::Client>;
+    /// Comment to prefix generated statements with, if any.
+    fn sql_comment(&self) -> Option<&SqlComment>;
 }
 
 #[async_trait]
@@ -176,6 +206,7 @@ async fn query<'c>(
         order: Option<&[query::Order]>,
     ) -> Result<RawQueryResult<'c>> {
         let mut sqlquery = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sqlquery);
         helper::sql_select(columns, table, &mut sqlquery);
         let mut values: Vec<SqlVal> = Vec::new();
         if let Some(expr) = expr {
@@ -228,6 +259,7 @@ async fn insert_returning_pk(
         values: &[SqlValRef<'_>],
     ) -> Result<SqlVal> {
         let mut sql = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sql);
         helper::sql_insert_with_placeholders(
             table,
             columns,
@@ -259,6 +291,7 @@ async fn insert_only(
         values: &[SqlValRef<'_>],
     ) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sql);
         helper::sql_insert_with_placeholders(
             table,
             columns,
@@ -278,6 +311,7 @@ async fn insert_or_replace(
         values: &[SqlValRef<'_>],
     ) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sql);
         sql_insert_or_replace_with_placeholders(table, columns, pkcol, &mut sql);
         let params: Vec<&DynToSqlPg> = values.iter().map(|v| v as &DynToSqlPg).collect();
         let future = self.client()?.execute(sql.as_str(), params.as_slice());
@@ -293,6 +327,7 @@ async fn update(
         values: &[SqlValRef<'_>],
     ) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sql);
         helper::sql_update_with_placeholders(
             table,
             pkcol,
@@ -319,6 +354,7 @@ async fn delete(&self, table: &str, pkcol: &'static str, pk: SqlVal) -> Result<(
     }
     async fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
         let mut sql = String::new();
+        helper::sql_comment(self.sql_comment(), &mut sql);
         let mut values: Vec<SqlVal> = Vec::new();
         write!(
             &mut sql,
@@ -348,14 +384,77 @@ async fn has_table(&self, table: &str) -> Result<bool> {
         let rows = future.await?;
         Ok(!rows.is_empty())
     }
+    async fn introspect_table(&self, table: &str) -> Result<Option<ATable>> {
+        // future improvement, should be schema-aware
+        let future = self.client()?.prepare(
+            "SELECT c.column_name, c.data_type, c.udt_name, c.is_nullable, c.column_default, \
+             EXISTS (SELECT 1 FROM information_schema.table_constraints tc \
+             JOIN information_schema.key_column_usage k \
+             ON tc.constraint_name = k.constraint_name AND tc.table_name = k.table_name \
+             WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_name = c.table_name \
+             AND k.column_name = c.column_name) \
+             FROM information_schema.columns c WHERE c.table_name = $1 \
+             ORDER BY c.ordinal_position;",
+        );
+        let stmt = future.await?;
+        let folded = folded_identifier(table);
+        let tableref: &[&(dyn postgres::types::ToSql + Sync)] = &[&folded];
+        let future = self.client()?.query(&stmt, tableref);
+        let rows = future.await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let mut atable = ATable::new(table.to_string());
+        for row in rows {
+            let name: String = row.try_get(0)?;
+            let data_type: String = row.try_get(1)?;
+            let udt_name: String = row.try_get(2)?;
+            let is_nullable: String = row.try_get(3)?;
+            let default: Option<String> = row.try_get(4)?;
+            let pk: bool = row.try_get(5)?;
+            let serial = default.is_some_and(|d| d.starts_with("nextval("));
+            let ty = match data_type.as_str() {
+                "integer" if serial => "SERIAL".to_string(),
+                "bigint" if serial => "BIGSERIAL".to_string(),
+                "timestamp without time zone" => "TIMESTAMP".to_string(),
+                "USER-DEFINED" => udt_name.to_uppercase(),
+                _ => data_type.to_uppercase(),
+            };
+            atable.add_column(AColumn::new(
+                name,
+                DeferredSqlType::KnownId(TypeIdentifier::Name(ty)),
+                is_nullable == "YES",
+                pk,
+                serial,
+                false,
+                None,
+                None,
+            ));
+        }
+        Ok(Some(atable))
+    }
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        let future = self.client()?.query(
+            "SELECT table_name::text FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' \
+             ORDER BY table_name;",
+            &[],
+        );
+        let rows = future.await?;
+        rows.iter().map(|row| Ok(row.try_get(0)?)).collect()
+    }
 }
 
 struct PgTransaction<'c> {
     trans: Option<postgres::Transaction<'c>>,
+    comment: Option<SqlComment>,
 }
 impl<'c> PgTransaction<'c> {
-    fn new(trans: postgres::Transaction<'c>) -> Self {
-        PgTransaction { trans: Some(trans) }
+    fn new(trans: postgres::Transaction<'c>, comment: Option<SqlComment>) -> Self {
+        PgTransaction {
+            trans: Some(trans),
+            comment,
+        }
     }
     fn get(&self) -> Result<&postgres::Transaction<'c>> {
         match &self.trans {
@@ -381,6 +480,9 @@ impl<'c> PgConnectionLike for PgTransaction<'c> {
     fn client(&self) -> Result<&Self::Client> {
         self.get()
     }
+    fn sql_comment(&self) -> Option<&SqlComment> {
+        self.comment.as_ref()
+    }
 }
 
 #[async_trait]
@@ -398,6 +500,9 @@ async fn rollback(&mut self) -> Result<()> {
             Some(trans) => Ok(trans.rollback().await?),
         }
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.comment = comment;
+    }
     // Workaround for https://github.com/rust-lang/rfcs/issues/2765
     fn connection_methods(&self) -> &dyn ConnectionMethods {
         self
@@ -440,8 +545,12 @@ fn to_sql(
             Blob(b) => b.to_sql_checked(requested_ty, out),
             #[cfg(feature = "json")]
             Json(v) => v.to_sql_checked(requested_ty, out),
+            #[cfg(feature = "decimal")]
+            Decimal(d) => d.to_sql_checked(requested_ty, out),
             #[cfg(feature = "datetime")]
             Timestamp(dt) => dt.to_sql_checked(requested_ty, out),
+            #[cfg(feature = "datetime")]
+            Date(d) => d.to_sql_checked(requested_ty, out),
             Null => Ok(postgres::types::IsNull::Yes),
             Custom(SqlValRefCustom::PgToSql { ty, tosql }) => {
                 check_type_match(ty, requested_ty)?;
@@ -497,8 +606,14 @@ fn from_sql(
             Type::JSONB => Ok(SqlValRef::Json(postgres::types::FromSql::from_sql(
                 ty, raw,
             )?)),
+            #[cfg(feature = "decimal")]
+            Type::NUMERIC => Ok(SqlValRef::Decimal(postgres::types::FromSql::from_sql(
+                ty, raw,
+            )?)),
             #[cfg(feature = "datetime")]
             Type::TIMESTAMP => Ok(SqlValRef::Timestamp(NaiveDateTime::from_sql(ty, raw)?)),
+            #[cfg(feature = "datetime")]
+            Type::DATE => Ok(SqlValRef::Date(NaiveDate::from_sql(ty, raw)?)),
             _ => Ok(SqlValRef::Custom(SqlValRefCustom::PgBytes {
                 ty: ty.clone(),
                 data: raw,
@@ -596,6 +711,9 @@ fn sql_for_op(current: &mut ADB, op: &Operation) -> Result<String> {
                 Ok(String::new())
             }
         }
+        Operation::ChangeTableChecks(tbl, old, new) => Ok(change_table_checks(tbl, old, new)),
+        Operation::AddIndex(tbl, index) => Ok(helper::create_index(tbl, index)),
+        Operation::RemoveIndex(_tbl, name) => Ok(helper::drop_index(name)),
     }
 }
 
@@ -606,6 +724,19 @@ fn create_table(table: &ATable, allow_exists: bool) -> Result<String> {
         .map(define_column)
         .collect::<Result<Vec<String>>>()?
         .join(",\n");
+    let checks: String = table
+        .checks
+        .iter()
+        .enumerate()
+        .map(|(idx, check)| {
+            format!(
+                ",\nCONSTRAINT {} {}",
+                helper::quote_reserved_word(&table_check_name(&table.name, idx)),
+                helper::check_constraint(check)
+            )
+        })
+        .collect();
+    let coldefs = coldefs + &checks;
     let modifier = if allow_exists { "IF NOT EXISTS " } else { "" };
     Ok(format!(
         "CREATE TABLE {}{} (\n{}\n);",
@@ -646,6 +777,9 @@ fn define_column(col: &AColumn) -> Result<String> {
     if col.unique() {
         constraints.push("UNIQUE".to_string());
     }
+    if let Some(check) = col.check() {
+        constraints.push(helper::check_constraint(check));
+    }
     if constraints.is_empty() {
         return Ok(format!(
             "{} {}",
@@ -661,6 +795,15 @@ fn define_column(col: &AColumn) -> Result<String> {
     ))
 }
 
+/// The name under which postgres stores the identifier `name`, given that
+/// unquoted identifiers are folded to lower case.
+fn folded_identifier(name: &str) -> String {
+    match helper::quote_reserved_word(name) {
+        Cow::Borrowed(_) => name.to_lowercase(),
+        Cow::Owned(_) => name.to_string(),
+    }
+}
+
 fn define_fkey_constraint(table_name: &str, column: &AColumn) -> String {
     let reference = column
         .reference()
@@ -704,9 +847,13 @@ fn col_sqltype(col: &AColumn) -> Result<Cow<str>> {
                     SqlType::Text => Cow::Borrowed("TEXT"),
                     #[cfg(feature = "datetime")]
                     SqlType::Timestamp => Cow::Borrowed("TIMESTAMP"),
+                    #[cfg(feature = "datetime")]
+                    SqlType::Date => Cow::Borrowed("DATE"),
                     SqlType::Blob => Cow::Borrowed("BYTEA"),
                     #[cfg(feature = "json")]
                     SqlType::Json => Cow::Borrowed("JSONB"),
+                    #[cfg(feature = "decimal")]
+                    SqlType::Decimal => Cow::Borrowed("NUMERIC"),
                     SqlType::Custom(c) => match c {
                         SqlTypeCustom::Pg(ref ty) => Cow::Owned(ty.name().to_string()),
                     },
@@ -716,6 +863,28 @@ fn col_sqltype(col: &AColumn) -> Result<Cow<str>> {
     }
 }
 
+/// The [`SqlType`] read into for a column whose type is reported by
+/// introspection as `name`, if there is one.
+fn sqltype_from_name(name: &str) -> Option<SqlType> {
+    match name {
+        "BOOLEAN" => Some(SqlType::Bool),
+        "INTEGER" | "SERIAL" => Some(SqlType::Int),
+        "BIGINT" | "BIGSERIAL" => Some(SqlType::BigInt),
+        "DOUBLE PRECISION" => Some(SqlType::Real),
+        "TEXT" | "CHARACTER VARYING" | "CHARACTER" => Some(SqlType::Text),
+        #[cfg(feature = "datetime")]
+        "TIMESTAMP" => Some(SqlType::Timestamp),
+        #[cfg(feature = "datetime")]
+        "DATE" => Some(SqlType::Date),
+        "BYTEA" => Some(SqlType::Blob),
+        #[cfg(feature = "json")]
+        "JSONB" => Some(SqlType::Json),
+        #[cfg(feature = "decimal")]
+        "NUMERIC" => Some(SqlType::Decimal),
+        _ => None,
+    }
+}
+
 fn drop_table(name: &str) -> String {
     format!("DROP TABLE {};", helper::quote_reserved_word(name))
 }
@@ -834,6 +1003,27 @@ fn change_column(table: &ATable, old: &AColumn, new: &AColumn) -> Result<String>
         });
     }
 
+    if old.check() != new.check() {
+        if old.check().is_some() {
+            // Standard constraint naming scheme
+            stmts.push(format!(
+                "ALTER TABLE {} DROP CONSTRAINT {}_{}_check;",
+                quote_reserved_word(tbl_name),
+                tbl_name,
+                old.name()
+            ));
+        }
+        if let Some(check) = new.check() {
+            stmts.push(format!(
+                "ALTER TABLE {} ADD CONSTRAINT {}_{}_check {};",
+                quote_reserved_word(tbl_name),
+                tbl_name,
+                new.name(),
+                helper::check_constraint(check)
+            ));
+        }
+    }
+
     if old.reference() != new.reference() {
         if old.reference().is_some() {
             // Drop the old reference
@@ -853,6 +1043,29 @@ fn change_column(table: &ATable, old: &AColumn, new: &AColumn) -> Result<String>
     Ok(result)
 }
 
+/// Name given to the table-level CHECK constraint at position `idx`.
+fn table_check_name(tbl_name: &str, idx: usize) -> String {
+    format!("{tbl_name}_check{idx}")
+}
+
+fn change_table_checks(tbl_name: &str, old: &[String], new: &[String]) -> String {
+    let tbl = helper::quote_reserved_word(tbl_name);
+    let drops = (0..old.len()).map(|idx| {
+        format!(
+            "ALTER TABLE {tbl} DROP CONSTRAINT {};",
+            helper::quote_reserved_word(&table_check_name(tbl_name, idx))
+        )
+    });
+    let adds = new.iter().enumerate().map(|(idx, check)| {
+        format!(
+            "ALTER TABLE {tbl} ADD CONSTRAINT {} {};",
+            helper::quote_reserved_word(&table_check_name(tbl_name, idx)),
+            helper::check_constraint(check)
+        )
+    });
+    drops.chain(adds).collect::<Vec<String>>().join("\n")
+}
+
 pub fn sql_insert_or_replace_with_placeholders(
     table: &str,
     columns: &[Column],
@@ -897,8 +1110,12 @@ fn pgtype_for_val(val: &SqlVal) -> postgres::types::Type {
         Some(SqlType::Blob) => postgres::types::Type::BYTEA,
         #[cfg(feature = "json")]
         Some(SqlType::Json) => postgres::types::Type::JSON,
+        #[cfg(feature = "decimal")]
+        Some(SqlType::Decimal) => postgres::types::Type::NUMERIC,
         #[cfg(feature = "datetime")]
         Some(SqlType::Timestamp) => postgres::types::Type::TIMESTAMP,
+        #[cfg(feature = "datetime")]
+        Some(SqlType::Date) => postgres::types::Type::DATE,
         Some(SqlType::Custom(inner)) => match inner {
             #[cfg(feature = "pg")]
             SqlTypeCustom::Pg(ty, ..) => ty,
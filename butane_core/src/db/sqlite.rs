@@ -6,25 +6,30 @@
 use std::pin::Pin;
 #[cfg(feature = "log")]
 use std::sync::Once;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 #[cfg(feature = "datetime")]
-use chrono::naive::NaiveDateTime;
+use chrono::naive::{NaiveDate, NaiveDateTime};
 use fallible_streaming_iterator::FallibleStreamingIterator;
 use pin_project::pin_project;
 
 #[cfg(feature = "async")]
 use super::ConnectionAsync;
 use super::{helper, Backend, BackendRow, Column, RawQueryResult};
-use super::{BackendConnection, BackendTransaction, Connection, ConnectionMethods, Transaction};
+use super::{
+    BackendConnection, BackendTransaction, Connection, ConnectionMethods, SqlComment, Transaction,
+};
 use crate::db::connmethods::BackendRows;
 use crate::migrations::adb::ARef;
-use crate::migrations::adb::{AColumn, ATable, Operation, TypeIdentifier, ADB};
+use crate::migrations::adb::{AColumn, ATable, DeferredSqlType, Operation, TypeIdentifier, ADB};
 use crate::query::{BoolExpr, Order};
 use crate::{debug, query, Error, Result, SqlType, SqlVal, SqlValRef};
 
 #[cfg(feature = "datetime")]
 const SQLITE_DT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+#[cfg(feature = "datetime")]
+const SQLITE_DATE_FORMAT: &str = "%Y-%m-%d";
 
 /// The name of the sqlite backend.
 pub const BACKEND_NAME: &str = "sqlite";
@@ -47,7 +52,22 @@ fn log_callback(error_code: std::ffi::c_int, message: &str) {
     }
 }
 
+/// Pragmas which may be set by query parameters on the connection path.
+const PRAGMAS: [&str; 4] = [
+    "busy_timeout",
+    "foreign_keys",
+    "journal_mode",
+    "synchronous",
+];
+
 /// SQLite [`Backend`] implementation.
+///
+/// The connection string is the path of the database file, optionally
+/// followed by query parameters setting any of the pragmas
+/// `busy_timeout`, `foreign_keys`, `journal_mode` and `synchronous`,
+/// e.g. `foo.db?journal_mode=WAL&busy_timeout=5000`. Other parameters
+/// are left in place, for use with `file:` URIs. Foreign key
+/// enforcement is on unless disabled with `foreign_keys=OFF`.
 #[derive(Debug, Default, Clone)]
 pub struct SQLiteBackend;
 impl SQLiteBackend {
@@ -57,12 +77,46 @@ pub fn new() -> SQLiteBackend {
 }
 impl SQLiteBackend {
     fn connect(&self, path: &str) -> Result<SQLiteConnection> {
-        let connection = SQLiteConnection::open(Path::new(path))?;
-        connection.execute("PRAGMA foreign_keys = ON")?;
+        let (path, pragmas) = split_pragmas(path)?;
+        let connection = SQLiteConnection::open(Path::new(&path))?;
+        if !pragmas.iter().any(|(name, _)| *name == "foreign_keys") {
+            connection.execute("PRAGMA foreign_keys = ON")?;
+        }
+        for (name, value) in pragmas {
+            connection.execute(&format!("PRAGMA {name} = {value}"))?;
+        }
         Ok(connection)
     }
 }
 
+/// Splits the query parameters naming [`PRAGMAS`] off `path`,
+/// returning the path to open and the pragmas to set.
+fn split_pragmas(path: &str) -> Result<(String, Vec<(&str, &str)>)> {
+    let Some((file, query)) = path.split_once('?') else {
+        return Ok((path.to_string(), Vec::new()));
+    };
+    let mut pragmas = Vec::new();
+    let mut params = Vec::new();
+    for param in query.split('&') {
+        match param.split_once('=') {
+            Some((name, value)) if PRAGMAS.contains(&name) => {
+                // The value is interpolated into the PRAGMA statement.
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(Error::InvalidConnectionParam(param.to_string()));
+                }
+                pragmas.push((name, value));
+            }
+            _ => params.push(param),
+        }
+    }
+    let path = if params.is_empty() {
+        file.to_string()
+    } else {
+        format!("{file}?{}", params.join("&"))
+    };
+    Ok((path, pragmas))
+}
+
 #[async_trait]
 impl Backend for SQLiteBackend {
     fn name(&self) -> &'static str {
@@ -83,6 +137,14 @@ fn create_migration_sql(&self, current: &ADB, ops: Vec<Operation>) -> Result<Str
         Ok(lines.join("\n"))
     }
 
+    fn column_type_name(&self, col: &AColumn) -> Result<String> {
+        Ok(col_sqltype(col).into_owned())
+    }
+
+    fn read_schema(&self, conn: &dyn ConnectionMethods) -> Result<ADB> {
+        helper::read_schema(conn, sqltype_from_name)
+    }
+
     fn connect(&self, path: &str) -> Result<Connection> {
         Ok(Connection {
             conn: Box::new(self.connect(path)?),
@@ -99,10 +161,16 @@ async fn connect_async(&self, _path: &str) -> Result<ConnectionAsync> {
     }
 }
 
+/// Number of virtual machine instructions between checks of the
+/// statement timeout.
+const TIMEOUT_CHECK_INTERVAL: i32 = 1000;
+
 /// SQLite database connection.
 #[derive(Debug)]
 pub struct SQLiteConnection {
     conn: rusqlite::Connection,
+    comment: Option<SqlComment>,
+    timeout: Option<Duration>,
 }
 impl SQLiteConnection {
     fn open(path: impl AsRef<Path>) -> Result<Self> {
@@ -115,20 +183,27 @@ fn open(path: impl AsRef<Path>) -> Result<Self> {
         });
 
         rusqlite::Connection::open(path)
-            .map(|conn| SQLiteConnection { conn })
+            .map(|conn| SQLiteConnection {
+                conn,
+                comment: None,
+                timeout: None,
+            })
             .map_err(|e| e.into())
     }
 
-    // For use with connection_method_wrapper macro
     #[allow(clippy::unnecessary_wraps)]
-    fn wrapped_connection_methods(&self) -> Result<&rusqlite::Connection> {
-        Ok(&self.conn)
+    fn wrapped_connection_methods(&self) -> Result<SQLiteConnRef<'_>> {
+        Ok(SQLiteConnRef::new(
+            &self.conn,
+            self.comment.as_ref(),
+            self.timeout,
+        ))
     }
 }
 
 impl ConnectionMethods for SQLiteConnection {
     fn execute(&self, sql: &str) -> Result<()> {
-        ConnectionMethods::execute(self.wrapped_connection_methods()?, sql)
+        self.wrapped_connection_methods()?.execute(sql)
     }
     fn query<'a, 'c>(
         &'c self,
@@ -186,12 +261,22 @@ fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     fn has_table(&self, table: &str) -> Result<bool> {
         self.wrapped_connection_methods()?.has_table(table)
     }
+    fn introspect_table(&self, table: &str) -> Result<Option<ATable>> {
+        self.wrapped_connection_methods()?.introspect_table(table)
+    }
+    fn list_tables(&self) -> Result<Vec<String>> {
+        self.wrapped_connection_methods()?.list_tables()
+    }
 }
 
 impl BackendConnection for SQLiteConnection {
     fn transaction(&mut self) -> Result<Transaction<'_>> {
         let trans: rusqlite::Transaction<'_> = self.conn.transaction()?;
-        let trans = Box::new(SqliteTransaction::new(trans));
+        let trans = Box::new(SqliteTransaction::new(
+            trans,
+            self.comment.clone(),
+            self.timeout,
+        ));
         Ok(Transaction::new(trans))
     }
     fn backend(&self) -> Box<dyn Backend> {
@@ -203,27 +288,134 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         false
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.comment = comment;
+    }
+    fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        if timeout.is_none() {
+            self.conn.progress_handler(0, None::<fn() -> bool>);
+        }
+        self.timeout = timeout;
+        Ok(())
+    }
 }
 
 impl ConnectionMethods for rusqlite::Connection {
+    fn execute(&self, sql: &str) -> Result<()> {
+        SQLiteConnRef::new(self, None, None).execute(sql)
+    }
+    fn query<'c>(
+        &'c self,
+        table: &str,
+        columns: &[Column],
+        expr: Option<BoolExpr>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        order: Option<&[Order]>,
+    ) -> Result<RawQueryResult<'c>> {
+        SQLiteConnRef::new(self, None, None).query(table, columns, expr, limit, offset, order)
+    }
+    fn insert_returning_pk(
+        &self,
+        table: &str,
+        columns: &[Column],
+        pkcol: &Column,
+        values: &[SqlValRef<'_>],
+    ) -> Result<SqlVal> {
+        SQLiteConnRef::new(self, None, None).insert_returning_pk(table, columns, pkcol, values)
+    }
+    fn insert_only(&self, table: &str, columns: &[Column], values: &[SqlValRef<'_>]) -> Result<()> {
+        SQLiteConnRef::new(self, None, None).insert_only(table, columns, values)
+    }
+    fn insert_or_replace(
+        &self,
+        table: &str,
+        columns: &[Column],
+        pkcol: &Column,
+        values: &[SqlValRef],
+    ) -> Result<()> {
+        SQLiteConnRef::new(self, None, None).insert_or_replace(table, columns, pkcol, values)
+    }
+    fn update(
+        &self,
+        table: &str,
+        pkcol: Column,
+        pk: SqlValRef,
+        columns: &[Column],
+        values: &[SqlValRef<'_>],
+    ) -> Result<()> {
+        SQLiteConnRef::new(self, None, None).update(table, pkcol, pk, columns, values)
+    }
+    fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
+        SQLiteConnRef::new(self, None, None).delete_where(table, expr)
+    }
+    fn has_table(&self, table: &str) -> Result<bool> {
+        SQLiteConnRef::new(self, None, None).has_table(table)
+    }
+    fn introspect_table(&self, table: &str) -> Result<Option<ATable>> {
+        SQLiteConnRef::new(self, None, None).introspect_table(table)
+    }
+    fn list_tables(&self) -> Result<Vec<String>> {
+        SQLiteConnRef::new(self, None, None).list_tables()
+    }
+}
+
+/// A rusqlite connection (or transaction) together with the comment,
+/// if any, to prefix generated statements with and the statement
+/// timeout, if any. Implements the operations of [`ConnectionMethods`]
+/// for both [`SQLiteConnection`] and [`SqliteTransaction`].
+#[derive(Clone, Copy)]
+struct SQLiteConnRef<'a> {
+    conn: &'a rusqlite::Connection,
+    comment: Option<&'a SqlComment>,
+    timeout: Option<Duration>,
+}
+
+impl<'a> SQLiteConnRef<'a> {
+    fn new(
+        conn: &'a rusqlite::Connection,
+        comment: Option<&'a SqlComment>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        SQLiteConnRef {
+            conn,
+            comment,
+            timeout,
+        }
+    }
+
+    /// Starts the statement timeout, if any, for a statement about to
+    /// run. SQLite interrupts the statement once it has passed.
+    fn start_timeout(&self) {
+        if let Some(timeout) = self.timeout {
+            let deadline = Instant::now() + timeout;
+            self.conn.progress_handler(
+                TIMEOUT_CHECK_INTERVAL,
+                Some(move || Instant::now() >= deadline),
+            );
+        }
+    }
+
     fn execute(&self, sql: &str) -> Result<()> {
         if cfg!(feature = "log") {
             debug!("execute sql {}", sql);
         }
-        self.execute_batch(sql.as_ref())?;
+        self.start_timeout();
+        self.conn.execute_batch(sql.as_ref())?;
         Ok(())
     }
 
-    fn query<'c>(
-        &'c self,
+    fn query(
+        &self,
         table: &str,
         columns: &[Column],
         expr: Option<BoolExpr>,
         limit: Option<i32>,
         offset: Option<i32>,
         order: Option<&[Order]>,
-    ) -> Result<RawQueryResult<'c>> {
+    ) -> Result<RawQueryResult<'a>> {
         let mut sqlquery = String::new();
+        helper::sql_comment(self.comment, &mut sqlquery);
         helper::sql_select(columns, table, &mut sqlquery);
         let mut values: Vec<SqlVal> = Vec::new();
         if let Some(expr) = expr {
@@ -258,7 +450,8 @@ fn query<'c>(
         #[cfg(feature = "debug")]
         debug!("values {:?}", values);
 
-        let stmt = self.prepare(&sqlquery)?;
+        self.start_timeout();
+        let stmt = self.conn.prepare(&sqlquery)?;
         let adapter = QueryAdapter::new(stmt, rusqlite::params_from_iter(values))?;
         Ok(Box::new(adapter))
     }
@@ -270,6 +463,7 @@ fn insert_returning_pk(
         values: &[SqlValRef<'_>],
     ) -> Result<SqlVal> {
         let mut sql = String::new();
+        helper::sql_comment(self.comment, &mut sql);
         helper::sql_insert_with_placeholders(
             table,
             columns,
@@ -281,8 +475,10 @@ fn insert_returning_pk(
             #[cfg(feature = "debug")]
             debug!("values {:?}", values);
         }
-        self.execute(&sql, rusqlite::params_from_iter(values))?;
-        let pk: SqlVal = self.query_row_and_then(
+        self.start_timeout();
+        self.conn
+            .execute(&sql, rusqlite::params_from_iter(values))?;
+        let pk: SqlVal = self.conn.query_row_and_then(
             &format!(
                 "SELECT {} FROM {} WHERE ROWID = last_insert_rowid()",
                 pkcol.name(),
@@ -295,6 +491,7 @@ fn insert_returning_pk(
     }
     fn insert_only(&self, table: &str, columns: &[Column], values: &[SqlValRef<'_>]) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.comment, &mut sql);
         helper::sql_insert_with_placeholders(
             table,
             columns,
@@ -306,7 +503,9 @@ fn insert_only(&self, table: &str, columns: &[Column], values: &[SqlValRef<'_>])
             #[cfg(feature = "debug")]
             debug!("values {:?}", values);
         }
-        self.execute(&sql, rusqlite::params_from_iter(values))?;
+        self.start_timeout();
+        self.conn
+            .execute(&sql, rusqlite::params_from_iter(values))?;
         Ok(())
     }
     fn insert_or_replace(
@@ -317,8 +516,11 @@ fn insert_or_replace(
         values: &[SqlValRef],
     ) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.comment, &mut sql);
         sql_insert_or_update(table, columns, pkcol, &mut sql);
-        self.execute(&sql, rusqlite::params_from_iter(values))?;
+        self.start_timeout();
+        self.conn
+            .execute(&sql, rusqlite::params_from_iter(values))?;
         Ok(())
     }
     fn update(
@@ -330,6 +532,7 @@ fn update(
         values: &[SqlValRef<'_>],
     ) -> Result<()> {
         let mut sql = String::new();
+        helper::sql_comment(self.comment, &mut sql);
         helper::sql_update_with_placeholders(
             table,
             pkcol,
@@ -343,11 +546,18 @@ fn update(
             #[cfg(feature = "debug")]
             debug!("placeholders {:?}", placeholder_values);
         }
-        self.execute(&sql, rusqlite::params_from_iter(placeholder_values))?;
+        self.start_timeout();
+        self.conn
+            .execute(&sql, rusqlite::params_from_iter(placeholder_values))?;
+        Ok(())
+    }
+    fn delete(&self, table: &str, pkcol: &'static str, pk: SqlVal) -> Result<()> {
+        self.delete_where(table, BoolExpr::Eq(pkcol, query::Expr::Val(pk)))?;
         Ok(())
     }
     fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
         let mut sql = String::new();
+        helper::sql_comment(self.comment, &mut sql);
         let mut values: Vec<SqlVal> = Vec::new();
         write!(
             &mut sql,
@@ -366,24 +576,81 @@ fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
             #[cfg(feature = "debug")]
             debug!("placeholders {:?}", values);
         }
-        let cnt = self.execute(&sql, rusqlite::params_from_iter(values))?;
+        self.start_timeout();
+        let cnt = self
+            .conn
+            .execute(&sql, rusqlite::params_from_iter(values))?;
         Ok(cnt)
     }
     fn has_table(&self, table: &str) -> Result<bool> {
-        let mut stmt =
-            self.prepare("SELECT name FROM sqlite_master WHERE type='table' AND name=?;")?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM sqlite_master WHERE type='table' AND name=?;")?;
         let mut rows = stmt.query([table])?;
         Ok(rows.next()?.is_some())
     }
+    fn introspect_table(&self, table: &str) -> Result<Option<ATable>> {
+        if !self.has_table(table)? {
+            return Ok(None);
+        }
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, type, \"notnull\", pk FROM pragma_table_info(?);")?;
+        let columns = stmt
+            .query_map([table], |row| {
+                let name: String = row.get(0)?;
+                let ty: String = row.get(1)?;
+                let notnull: bool = row.get(2)?;
+                let pk: i64 = row.get(3)?;
+                Ok((name, ty.to_uppercase(), notnull, pk > 0))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        // A sole INTEGER primary key is an alias for the rowid, and so
+        // is assigned automatically.
+        let single_pk = columns.iter().filter(|(.., pk)| *pk).count() == 1;
+        let mut atable = ATable::new(table.to_string());
+        for (name, ty, notnull, pk) in columns {
+            let auto = pk && single_pk && ty == "INTEGER";
+            atable.add_column(AColumn::new(
+                name,
+                DeferredSqlType::KnownId(TypeIdentifier::Name(ty)),
+                !notnull,
+                pk,
+                auto,
+                false,
+                None,
+                None,
+            ));
+        }
+        Ok(Some(atable))
+    }
+    fn list_tables(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name FROM sqlite_master \
+             WHERE type='table' AND name NOT LIKE 'sqlite_%' ORDER BY name;",
+        )?;
+        let names = stmt.query_map([], |row| row.get(0))?;
+        Ok(names.collect::<rusqlite::Result<Vec<String>>>()?)
+    }
 }
 
 #[derive(Debug)]
 struct SqliteTransaction<'c> {
     trans: Option<rusqlite::Transaction<'c>>,
+    comment: Option<SqlComment>,
+    timeout: Option<Duration>,
 }
 impl<'c> SqliteTransaction<'c> {
-    fn new(trans: rusqlite::Transaction<'c>) -> Self {
-        SqliteTransaction { trans: Some(trans) }
+    fn new(
+        trans: rusqlite::Transaction<'c>,
+        comment: Option<SqlComment>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        SqliteTransaction {
+            trans: Some(trans),
+            comment,
+            timeout,
+        }
     }
     fn get(&self) -> Result<&rusqlite::Transaction<'c>> {
         match &self.trans {
@@ -391,8 +658,12 @@ fn get(&self) -> Result<&rusqlite::Transaction<'c>> {
             Some(trans) => Ok(trans),
         }
     }
-    fn wrapped_connection_methods(&self) -> Result<&rusqlite::Connection> {
-        Ok(self.get()?.deref())
+    fn wrapped_connection_methods(&self) -> Result<SQLiteConnRef<'_>> {
+        Ok(SQLiteConnRef::new(
+            self.get()?.deref(),
+            self.comment.as_ref(),
+            self.timeout,
+        ))
     }
     fn already_consumed() -> Error {
         Error::Internal("transaction has already been consumed".to_string())
@@ -400,7 +671,7 @@ fn already_consumed() -> Error {
 }
 impl ConnectionMethods for SqliteTransaction<'_> {
     fn execute(&self, sql: &str) -> Result<()> {
-        ConnectionMethods::execute(self.wrapped_connection_methods()?, sql)
+        self.wrapped_connection_methods()?.execute(sql)
     }
     fn query<'c>(
         &'c self,
@@ -458,6 +729,12 @@ fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     fn has_table(&self, table: &str) -> Result<bool> {
         self.wrapped_connection_methods()?.has_table(table)
     }
+    fn introspect_table(&self, table: &str) -> Result<Option<ATable>> {
+        self.wrapped_connection_methods()?.introspect_table(table)
+    }
+    fn list_tables(&self) -> Result<Vec<String>> {
+        self.wrapped_connection_methods()?.list_tables()
+    }
 }
 
 impl<'c> BackendTransaction<'c> for SqliteTransaction<'c> {
@@ -473,6 +750,9 @@ fn rollback(&mut self) -> Result<()> {
             Some(trans) => Ok(trans.rollback()?),
         }
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.comment = comment;
+    }
     // Workaround for https://github.com/rust-lang/rfcs/issues/2765
     fn connection_methods(&self) -> &dyn ConnectionMethods {
         self
@@ -505,11 +785,16 @@ fn sqlvalref_to_sqlite<'a>(valref: &SqlValRef<'a>) -> rusqlite::types::ToSqlOutp
         Json(v) => serde_json::to_string(v)
             .map(rusqlite::types::ToSqlOutput::from)
             .unwrap(),
+        // Stored as text to preserve both precision and scale.
+        #[cfg(feature = "decimal")]
+        Decimal(d) => Owned(Value::Text(d.to_string())),
         #[cfg(feature = "datetime")]
         Timestamp(dt) => {
             let f = dt.format(SQLITE_DT_FORMAT);
             Owned(Value::Text(f.to_string()))
         }
+        #[cfg(feature = "datetime")]
+        Date(d) => Owned(Value::Text(d.format(SQLITE_DATE_FORMAT).to_string())),
         Null => Owned(Value::Null),
         Custom(_) => panic!("Custom types not supported in sqlite"),
     }
@@ -618,11 +903,22 @@ fn sql_valref_from_rusqlite<'a>(
         SqlType::Text => SqlValRef::Text(val.as_str()?),
         #[cfg(feature = "json")]
         SqlType::Json => SqlValRef::Json(serde_json::from_str(val.as_str()?)?),
+        #[cfg(feature = "decimal")]
+        SqlType::Decimal => SqlValRef::Decimal(match val {
+            rusqlite::types::ValueRef::Integer(i) => rust_decimal::Decimal::from(i),
+            rusqlite::types::ValueRef::Real(r) => rust_decimal::Decimal::try_from(r)?,
+            _ => val.as_str()?.parse()?,
+        }),
         #[cfg(feature = "datetime")]
         SqlType::Timestamp => SqlValRef::Timestamp(NaiveDateTime::parse_from_str(
             val.as_str()?,
             SQLITE_DT_FORMAT,
         )?),
+        #[cfg(feature = "datetime")]
+        SqlType::Date => SqlValRef::Date(NaiveDate::parse_from_str(
+            val.as_str()?,
+            SQLITE_DATE_FORMAT,
+        )?),
         SqlType::Blob => SqlValRef::Blob(val.as_blob()?),
         SqlType::Custom(v) => return Err(Error::IncompatibleCustomT(v.clone(), BACKEND_NAME)),
     })
@@ -638,6 +934,9 @@ fn sql_for_op(current: &mut ADB, op: &Operation) -> Result<String> {
         Operation::AddColumn(tbl, col) => add_column(tbl, col),
         Operation::RemoveColumn(tbl, name) => Ok(remove_column(current, tbl, name)),
         Operation::ChangeColumn(tbl, old, new) => Ok(change_column(current, tbl, old, Some(new))),
+        Operation::ChangeTableChecks(tbl, _old, new) => Ok(change_table_checks(current, tbl, new)),
+        Operation::AddIndex(tbl, index) => Ok(helper::create_index(tbl, index)),
+        Operation::RemoveIndex(_tbl, name) => Ok(helper::drop_index(name)),
     }
 }
 
@@ -653,6 +952,9 @@ fn create_table(table: &ATable, allow_exists: bool) -> String {
     if !constraints.is_empty() {
         constraints = ",\n".to_owned() + &constraints;
     }
+    for check in &table.checks {
+        write!(constraints, ",\n{}", helper::check_constraint(check)).unwrap();
+    }
     format!(
         "CREATE TABLE {}{} (\n{}{}\n);",
         modifier, table.name, coldefs, constraints
@@ -685,6 +987,9 @@ fn define_column(col: &AColumn) -> String {
     if col.unique() {
         constraints.push("UNIQUE".to_string());
     }
+    if let Some(check) = col.check() {
+        constraints.push(helper::check_constraint(check));
+    }
     if constraints.is_empty() {
         format!(
             "{} {}",
@@ -739,12 +1044,41 @@ fn sqltype(ty: &SqlType) -> &'static str {
         SqlType::Blob => "BLOB",
         #[cfg(feature = "json")]
         SqlType::Json => "TEXT",
+        #[cfg(feature = "decimal")]
+        SqlType::Decimal => "TEXT",
         #[cfg(feature = "datetime")]
         SqlType::Timestamp => "TEXT",
+        #[cfg(feature = "datetime")]
+        SqlType::Date => "TEXT",
         SqlType::Custom(_) => panic!("Custom types not supported by sqlite backend"),
     }
 }
 
+/// The [`SqlType`] read into for a column declared with type `name`,
+/// following sqlite's rules for determining column affinity.
+fn sqltype_from_name(name: &str) -> Option<SqlType> {
+    let contains = |s: &[&str]| s.iter().any(|s| name.contains(s));
+    if contains(&["INT"]) {
+        Some(SqlType::BigInt)
+    } else if contains(&["CHAR", "CLOB", "TEXT"]) {
+        Some(SqlType::Text)
+    } else if name.is_empty() || contains(&["BLOB"]) {
+        Some(SqlType::Blob)
+    } else if contains(&["REAL", "FLOA", "DOUB"]) {
+        Some(SqlType::Real)
+    } else if name.starts_with("BOOL") {
+        Some(SqlType::Bool)
+    } else {
+        #[cfg(feature = "datetime")]
+        if name == "DATETIME" || name == "TIMESTAMP" {
+            return Some(SqlType::Timestamp);
+        } else if name == "DATE" {
+            return Some(SqlType::Date);
+        }
+        None
+    }
+}
+
 fn drop_table(name: &str) -> String {
     format!("DROP TABLE {};", helper::quote_reserved_word(name))
 }
@@ -811,23 +1145,59 @@ fn change_column(
         );
         return "".to_string();
     }
-    let old_table = table.unwrap();
-    let mut new_table = old_table.clone();
-    new_table.name = tmp_table_name(&new_table.name);
-    match new {
+    recreate_table(current, tbl_name, |new_table| match new {
         Some(col) => new_table.replace_column(col.clone()),
         None => new_table.remove_column(old.name()),
+    })
+}
+
+fn change_table_checks(current: &mut ADB, tbl_name: &str, checks: &[String]) -> String {
+    if current.get_table(tbl_name).is_none() {
+        crate::warn!(
+            "Cannot change checks on table {} that does not exist",
+            tbl_name
+        );
+        return "".to_string();
     }
-    let stmts: [&str; 4] = [
-        &create_table(&new_table, false),
-        &copy_table(old_table, &new_table),
-        &drop_table(&old_table.name),
-        &format!(
+    recreate_table(current, tbl_name, |new_table| {
+        new_table.checks = checks.to_vec()
+    })
+}
+
+/// SQLite cannot alter most aspects of an existing table, so create a
+/// new table with the definition produced by `modify`, copy the data
+/// across and replace the old table with it.
+fn recreate_table(current: &mut ADB, tbl_name: &str, modify: impl FnOnce(&mut ATable)) -> String {
+    let old_table = current
+        .get_table(tbl_name)
+        .expect("table to recreate must exist");
+    let mut new_table = old_table.clone();
+    new_table.name = tmp_table_name(&new_table.name);
+    modify(&mut new_table);
+    // Dropping the old table drops its indexes, so recreate those
+    // which still apply once the new table has its final name.
+    new_table.indexes.retain(|index| {
+        index
+            .columns
+            .iter()
+            .all(|c| new_table.columns.iter().any(|col| col.name() == c))
+    });
+    let mut stmts: Vec<String> = vec![
+        create_table(&new_table, false),
+        copy_table(old_table, &new_table),
+        drop_table(&old_table.name),
+        format!(
             "ALTER TABLE {} RENAME TO {};",
             helper::quote_reserved_word(&new_table.name),
             helper::quote_reserved_word(tbl_name)
         ),
     ];
+    stmts.extend(
+        new_table
+            .indexes
+            .iter()
+            .map(|index| helper::create_index(tbl_name, index)),
+    );
     let result = stmts.join("\n");
     new_table.name.clone_from(&old_table.name);
     current.replace_table(new_table);
@@ -1,12 +1,13 @@
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
 use crate::db::{
     Backend, BackendConnection, BackendConnectionAsync, BackendTransaction,
     BackendTransactionAsync, Connection, ConnectionAsync, ConnectionMethods, RawQueryResult,
-    Transaction, TransactionAsync,
+    SqlComment, Transaction, TransactionAsync,
 };
 use crate::migrations::adb;
 use crate::query::{BoolExpr, Order};
@@ -138,6 +139,12 @@ fn delete_where(&self, table: &str, expr: BoolExpr) -> Result<usize> {
     fn has_table(&self, table: &str) -> Result<bool> {
         self.block_on(self.inner.has_table(table))
     }
+    fn introspect_table(&self, table: &str) -> Result<Option<adb::ATable>> {
+        self.block_on(self.inner.introspect_table(table))
+    }
+    fn list_tables(&self) -> Result<Vec<String>> {
+        self.block_on(self.inner.list_tables())
+    }
 }
 
 impl<T> BackendConnection for SyncAdapter<T>
@@ -167,6 +174,13 @@ fn backend_name(&self) -> &'static str {
     fn is_closed(&self) -> bool {
         self.inner.is_closed()
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.inner.set_sql_comment(comment)
+    }
+    fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.runtime_handle
+            .block_on(self.inner.set_statement_timeout(timeout))
+    }
 }
 
 impl<T> SyncAdapter<T>
@@ -188,6 +202,9 @@ fn commit(&mut self) -> Result<()> {
     fn rollback(&mut self) -> Result<()> {
         self.runtime_handle.block_on(self.inner.rollback())
     }
+    fn set_sql_comment(&mut self, comment: Option<SqlComment>) {
+        self.inner.set_sql_comment(comment)
+    }
     fn connection_methods(&self) -> &dyn ConnectionMethods {
         self
     }
@@ -204,6 +221,12 @@ fn name(&self) -> &'static str {
     fn create_migration_sql(&self, current: &adb::ADB, ops: Vec<adb::Operation>) -> Result<String> {
         self.inner.create_migration_sql(current, ops)
     }
+    fn column_type_name(&self, col: &adb::AColumn) -> Result<String> {
+        self.inner.column_type_name(col)
+    }
+    fn read_schema(&self, conn: &dyn ConnectionMethods) -> Result<adb::ADB> {
+        self.inner.read_schema(conn)
+    }
     fn connect(&self, conn_str: &str) -> Result<Connection> {
         let conn_async = self.block_on(self.inner.connect_async(conn_str))?;
         let conn = Connection {
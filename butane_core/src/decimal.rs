@@ -0,0 +1,41 @@
+//! rust_decimal support
+
+#![deny(missing_docs)]
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::{
+    Error::CannotConvertSqlVal, FieldType, FromSql, PrimaryKeyType, Result, SqlType, SqlVal,
+    SqlValRef, ToSql,
+};
+
+impl ToSql for Decimal {
+    fn to_sql(&self) -> SqlVal {
+        SqlVal::Decimal(*self)
+    }
+    fn to_sql_ref(&self) -> SqlValRef<'_> {
+        SqlValRef::Decimal(*self)
+    }
+}
+impl FromSql for Decimal {
+    fn from_sql_ref(valref: SqlValRef) -> Result<Self> {
+        match valref {
+            SqlValRef::Decimal(val) => return Ok(val),
+            // Backends without a native decimal type store the
+            // canonical string, which preserves the scale.
+            SqlValRef::Text(text) => {
+                if let Ok(val) = Decimal::from_str(text) {
+                    return Ok(val);
+                }
+            }
+            _ => (),
+        }
+        Err(CannotConvertSqlVal(SqlType::Decimal, valref.into()))
+    }
+}
+impl FieldType for Decimal {
+    type RefType = Self;
+    const SQLTYPE: SqlType = SqlType::Decimal;
+}
+impl PrimaryKeyType for Decimal {}
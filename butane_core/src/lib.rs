@@ -18,6 +18,10 @@
 pub mod query;
 pub mod sqlval;
 
+#[cfg(feature = "decimal")]
+pub mod decimal;
+#[cfg(feature = "time")]
+pub mod time;
 #[cfg(feature = "uuid")]
 pub mod uuid;
 
@@ -88,8 +92,9 @@ async fn save_many_to_many_async(
         fn save_many_to_many_sync(&mut self, conn: &impl ConnectionMethods) -> Result<()>;
 
         /// Returns the Sql values of all columns except not any auto columns.
+        /// Fails if a value cannot be represented in the database.
         /// Used internally. You are unlikely to need to call this directly.
-        fn non_auto_values(&self, include_pk: bool) -> Vec<SqlValRef>;
+        fn non_auto_values(&self, include_pk: bool) -> Result<Vec<SqlValRef>>;
     }
 }
 
@@ -186,7 +191,7 @@ async fn save(&mut self, conn: &impl ConnectionMethods) -> Result<()>
                     pkcol,
                     self.pk().to_sql_ref(),
                     Self::NON_AUTO_COLUMNS,
-                    &self.non_auto_values(false),
+                    &self.non_auto_values(false)?,
                 )
                 .await?;
             } else {
@@ -196,7 +201,7 @@ async fn save(&mut self, conn: &impl ConnectionMethods) -> Result<()>
                         Self::TABLE,
                         Self::NON_AUTO_COLUMNS,
                         &pkcol,
-                        &self.non_auto_values(true),
+                        &self.non_auto_values(true)?,
                     )
                     .await?;
                 self.pk_mut().initialize(pk)?;
@@ -207,7 +212,7 @@ async fn save(&mut self, conn: &impl ConnectionMethods) -> Result<()>
                 Self::TABLE,
                 Self::COLUMNS,
                 &pkcol,
-                &self.non_auto_values(true),
+                &self.non_auto_values(true)?,
             )
             .await?;
         }
@@ -258,6 +263,8 @@ pub enum Error {
     MigrationError(String),
     #[error("Unknown backend {0}")]
     UnknownBackend(String),
+    #[error("Invalid connection parameter {0}")]
+    InvalidConnectionParam(String),
     #[error("Range error")]
     OutOfRange,
     #[error("Internal logic error {0}")]
@@ -280,6 +287,8 @@ pub enum Error {
     SaveDeterminationNotSupported,
     #[error("This is a dummy poisoned connection.")]
     PoisonedConnection,
+    #[error("Statement exceeded the statement timeout")]
+    Timeout,
     #[error("Connect connect_async for synchronous backend {0}. To support this, enable the async-adapter feature.")]
     NoAsyncAdapter(&'static str),
     #[error("(De)serialization error {0}")]
@@ -288,16 +297,22 @@ pub enum Error {
     IO(#[from] std::io::Error),
     #[cfg(feature = "sqlite")]
     #[error("Sqlite error {0}")]
-    SQLite(#[from] rusqlite::Error),
+    SQLite(rusqlite::Error),
     #[cfg(feature = "sqlite")]
     #[error("Sqlite error {0}")]
     SQLiteFromSQL(rusqlite::types::FromSqlError),
     #[cfg(feature = "pg")]
     #[error("Postgres error {0}")]
-    Postgres(#[from] tokio_postgres::Error),
+    Postgres(tokio_postgres::Error),
+    #[cfg(feature = "mysql")]
+    #[error("MySQL error {0}")]
+    MySQL(mysql_async::Error),
     #[cfg(feature = "datetime")]
     #[error("Chrono error {0}")]
     Chrono(#[from] chrono::ParseError),
+    #[cfg(feature = "decimal")]
+    #[error("Decimal error {0}")]
+    Decimal(#[from] rust_decimal::Error),
     #[error("RefCell error {0}")]
     CellBorrow(#[from] std::cell::BorrowMutError),
     #[cfg(feature = "tls")]
@@ -316,6 +331,43 @@ pub enum Error {
     CrossbeamChannel,
 }
 
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        match e.sqlite_error_code() {
+            // Butane interrupts statements only when they time out.
+            Some(rusqlite::ErrorCode::OperationInterrupted) => Error::Timeout,
+            _ => Error::SQLite(e),
+        }
+    }
+}
+
+#[cfg(feature = "pg")]
+impl From<tokio_postgres::Error> for Error {
+    fn from(e: tokio_postgres::Error) -> Self {
+        // Also reported for statements cancelled by other means, such
+        // as pg_cancel_backend.
+        if e.code() == Some(&tokio_postgres::error::SqlState::QUERY_CANCELED) {
+            Error::Timeout
+        } else {
+            Error::Postgres(e)
+        }
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<mysql_async::Error> for Error {
+    fn from(e: mysql_async::Error) -> Self {
+        match &e {
+            // ER_QUERY_TIMEOUT on MySQL and ER_STATEMENT_TIMEOUT on MariaDB.
+            mysql_async::Error::Server(err) if err.code == 3024 || err.code == 1969 => {
+                Error::Timeout
+            }
+            _ => Error::MySQL(e),
+        }
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl From<rusqlite::types::FromSqlError> for Error {
     fn from(e: rusqlite::types::FromSqlError) -> Self {
@@ -364,11 +416,17 @@ pub enum SqlType {
     #[cfg(feature = "datetime")]
     /// Timestamp
     Timestamp,
+    #[cfg(feature = "datetime")]
+    /// Calendar date, without a time
+    Date,
     /// Blob
     Blob,
     #[cfg(feature = "json")]
     /// JSON
     Json,
+    #[cfg(feature = "decimal")]
+    /// Exact decimal number
+    Decimal,
     /// Custom SQL type
     Custom(SqlTypeCustom),
 }
@@ -383,9 +441,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             Text => "string",
             #[cfg(feature = "datetime")]
             Timestamp => "timestamp",
+            #[cfg(feature = "datetime")]
+            Date => "date",
             Blob => "blob",
             #[cfg(feature = "json")]
             Json => "json",
+            #[cfg(feature = "decimal")]
+            Decimal => "decimal",
             Custom(_) => "custom",
         }
         .fmt(f)
@@ -289,6 +289,21 @@ pub fn transform_with(&mut self, op: Operation) {
                     t.replace_column(new);
                 }
             }
+            ChangeTableChecks(table, _, new) => {
+                if let Some(t) = self.tables.get_mut(&table) {
+                    t.checks = new;
+                }
+            }
+            AddIndex(table, index) => {
+                if let Some(t) = self.tables.get_mut(&table) {
+                    t.add_index(index);
+                }
+            }
+            RemoveIndex(table, name) => {
+                if let Some(t) = self.tables.get_mut(&table) {
+                    t.remove_index(&name);
+                }
+            }
         }
     }
 }
@@ -298,12 +313,20 @@ pub fn transform_with(&mut self, op: Operation) {
 pub struct ATable {
     pub name: String,
     pub columns: Vec<AColumn>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<AIndex>,
+    /// Table-level CHECK constraint expressions. These are backend-specific
+    /// SQL, passed through verbatim.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<String>,
 }
 impl ATable {
     pub fn new(name: String) -> ATable {
         ATable {
             name,
             columns: Vec::new(),
+            indexes: Vec::new(),
+            checks: Vec::new(),
         }
     }
     pub fn add_column(&mut self, col: AColumn) {
@@ -325,6 +348,39 @@ pub fn remove_column(&mut self, name: &str) {
     pub fn pk(&self) -> Option<&AColumn> {
         self.columns.iter().find(|c| c.is_pk())
     }
+    pub fn add_index(&mut self, index: AIndex) {
+        if let Some(existing) = self.indexes.iter_mut().find(|i| i.name == index.name) {
+            *existing = index;
+        } else {
+            self.indexes.push(index);
+        }
+    }
+    pub fn index<'a>(&'a self, name: &str) -> Option<&'a AIndex> {
+        self.indexes.iter().find(|i| i.name == name)
+    }
+    pub fn remove_index(&mut self, name: &str) {
+        self.indexes.retain(|i| i.name != name);
+    }
+}
+
+/// Abstract representation of a (non-unique) secondary index on a table.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AIndex {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+impl AIndex {
+    pub fn new(name: impl Into<String>, columns: Vec<String>) -> Self {
+        AIndex {
+            name: name.into(),
+            columns,
+        }
+    }
+    /// Create an index named according to its table and columns.
+    pub fn new_default_name(table_name: &str, columns: Vec<String>) -> Self {
+        let name = format!("{}_{}_idx", table_name, columns.join("_"));
+        Self::new(name, columns)
+    }
 }
 
 /// SqlType which may not yet be known.
@@ -432,6 +488,10 @@ pub struct AColumn {
     /// Whether this column refers to another column.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     reference: Option<ARef>,
+    /// CHECK constraint expression for the column. This is
+    /// backend-specific SQL, passed through verbatim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    check: Option<String>,
 }
 impl AColumn {
     /// Create new column.
@@ -455,6 +515,7 @@ pub fn new(
             unique,
             default,
             reference,
+            check: None,
         }
     }
     /// Simple column that is non-null, non-auto, non-pk, non-unique with no default
@@ -488,6 +549,14 @@ pub fn add_reference(&mut self, reference: &ARef) {
     pub fn remove_reference(&mut self) {
         self.reference = None;
     }
+    /// Returns the CHECK constraint expression for this column, if any.
+    pub fn check(&self) -> Option<&str> {
+        self.check.as_deref()
+    }
+    /// Set the CHECK constraint expression for this column.
+    pub fn set_check(&mut self, check: Option<String>) {
+        self.check = check;
+    }
     /// Get the type identifier.
     pub fn typeid(&self) -> Result<TypeIdentifier> {
         match &self.sqltype {
@@ -590,12 +659,18 @@ pub enum Operation {
     RemoveTableConstraints(ATable),
     /// Remove named table.
     RemoveTable(String),
+    /// Remove the named index from the named table.
+    RemoveIndex(String, String),
     /// Add a table column.
     AddColumn(String, AColumn),
     /// Remove a table column.
     RemoveColumn(String, String),
     /// Change a table columns type.
     ChangeColumn(String, AColumn, AColumn),
+    /// Change the table-level CHECK constraints of the named table from the old to the new set.
+    ChangeTableChecks(String, Vec<String>, Vec<String>),
+    /// Add an index to the named table.
+    AddIndex(String, AIndex),
     /// Add table constraints referring to other tables, if the backend supports it.
     AddTableConstraints(ATable),
 }
@@ -637,6 +712,9 @@ pub fn diff(old: &ADB, new: &ADB) -> Vec<Operation> {
     for added in new_tables {
         let added: &str = added.as_ref();
         let table = new.tables.get(added).expect("no table");
+        for index in &table.indexes {
+            ops.push(Operation::AddIndex(table.name.clone(), index.clone()));
+        }
         if table.columns.iter().any(|x| x.reference.is_some()) {
             ops.push(Operation::AddTableConstraints(table.clone()));
         }
@@ -650,6 +728,15 @@ fn col_by_name<'a>(columns: &'a [AColumn], name: &str) -> Option<&'a AColumn> {
 
 fn diff_table(old: &ATable, new: &ATable) -> Vec<Operation> {
     let mut ops: Vec<Operation> = Vec::new();
+
+    // Remove indexes which no longer exist or have changed before
+    // touching any columns they may cover.
+    for index in &old.indexes {
+        if new.index(&index.name) != Some(index) {
+            ops.push(Operation::RemoveIndex(old.name.clone(), index.name.clone()));
+        }
+    }
+
     let new_names: BTreeSet<&String> = new.columns.iter().map(|c| &c.name).collect();
     let old_names: BTreeSet<&String> = old.columns.iter().map(|c| &c.name).collect();
 
@@ -685,5 +772,20 @@ fn diff_table(old: &ATable, new: &ATable) -> Vec<Operation> {
             col.clone(),
         ));
     }
+
+    if old.checks != new.checks {
+        ops.push(Operation::ChangeTableChecks(
+            new.name.clone(),
+            old.checks.clone(),
+            new.checks.clone(),
+        ));
+    }
+
+    // Add indexes which are new or have changed
+    for index in &new.indexes {
+        if old.index(&index.name) != Some(index) {
+            ops.push(Operation::AddIndex(new.name.clone(), index.clone()));
+        }
+    }
     ops
 }
@@ -14,6 +14,15 @@
 
 type SqlTypeMap = BTreeMap<TypeKey, DeferredSqlType>;
 const TYPES_FILENAME: &str = "types.json";
+/// Name (without the `.sql` extension) of the file holding the data
+/// migration SQL run after the schema changes when applying.
+const UP_DATA_NAME: &str = "up_data";
+/// Name (without the `.sql` extension) of the file holding the data
+/// migration SQL run before the schema changes when downgrading.
+const DOWN_DATA_NAME: &str = "down_data";
+/// Extension of the marker files recording tables explicitly deleted
+/// from the current migration.
+const DELETED_EXTENSION: &str = ".deleted";
 
 /// Metadata stored in each migration in the filesystem.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -91,7 +100,11 @@ fn delete_sql(&self, name: &str) -> Result<()> {
     }
 
     fn read_sql(&self, backend: &str, direction: &str) -> Result<Option<String>> {
-        let path = self.sql_path(backend, direction);
+        self.read_named_sql(&format!("{backend}_{direction}"))
+    }
+
+    fn read_named_sql(&self, name: &str) -> Result<Option<String>> {
+        let path = self.root.join(format!("{name}.sql"));
         let mut buf = String::new();
         if !path.is_file() {
             return Ok(None);
@@ -100,10 +113,6 @@ fn read_sql(&self, backend: &str, direction: &str) -> Result<Option<String>> {
         Ok(Some(buf))
     }
 
-    fn sql_path(&self, backend: &str, direction: &str) -> PathBuf {
-        self.root.join(format!("{backend}_{direction}.sql"))
-    }
-
     fn delete_file(&self, fname: &str) -> Result<()> {
         self.ensure_dir()?;
         let path = self.root.join(fname);
@@ -149,6 +158,7 @@ fn lock_shared(&self) -> Result<MigrationLock> {
 
     /// Delete all of the files except info.json which is recreated
     /// with only `from_name` set to allow migration series traversal.
+    /// Data migration SQL is kept, as it cannot be regenerated.
     pub fn delete_db(&self) -> Result<()> {
         let entries = self.fs.list_dir(&self.root)?;
         for entry in entries {
@@ -156,7 +166,11 @@ pub fn delete_db(&self) -> Result<()> {
                 None => continue,
                 Some(name) => {
                     let name = name.to_string_lossy();
-                    if name == "info.json" {
+                    if name == format!("{UP_DATA_NAME}.sql")
+                        || name == format!("{DOWN_DATA_NAME}.sql")
+                    {
+                        continue;
+                    } else if name == "info.json" {
                         // Re-create info.json using the minimum required to allow
                         // `all_migrations` to traverse the list.
                         let info = self.info()?;
@@ -177,6 +191,10 @@ pub fn delete_db(&self) -> Result<()> {
 
 impl MigrationMut for FsMigration {
     fn add_modified_table(&mut self, table: &ATable) -> Result<()> {
+        let deleted_marker = format!("{}{DELETED_EXTENSION}", table.name);
+        if self.root.join(&deleted_marker).is_file() {
+            self.delete_file(&deleted_marker)?;
+        }
         self.write_contents(
             &format!("{}.table", table.name),
             serde_json::to_string_pretty(table)?.as_bytes(),
@@ -214,8 +232,12 @@ fn delete_table(&mut self, table: &str) -> Result<()> {
         let fname = format!("{table}.table");
         self.ensure_dir()?;
         let path = self.root.join(fname);
-        std::fs::remove_file(path)?;
-        Ok(())
+        // The table may only exist in a previous migration, e.g. if the
+        // current migration was cleaned.
+        if path.is_file() {
+            std::fs::remove_file(path)?;
+        }
+        self.write_contents(&format!("{table}{DELETED_EXTENSION}"), table.as_bytes())
     }
 
     fn add_sql(&mut self, backend_name: &str, up_sql: &str, down_sql: &str) -> Result<()> {
@@ -230,6 +252,20 @@ fn add_sql(&mut self, backend_name: &str, up_sql: &str, down_sql: &str) -> Resul
         Ok(())
     }
 
+    fn set_data_sql(&mut self, up_sql: Option<&str>, down_sql: Option<&str>) -> Result<()> {
+        for (name, sql) in [(UP_DATA_NAME, up_sql), (DOWN_DATA_NAME, down_sql)] {
+            match sql {
+                Some(sql) if !sql.is_empty() => self.write_sql(name, sql)?,
+                _ => {
+                    if self.root.join(format!("{name}.sql")).is_file() {
+                        self.delete_sql(name)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn remove_sql(&mut self, backend_name: &str) -> Result<()> {
         self.delete_sql(&format!("{backend_name}_up"))?;
         self.delete_sql(&format!("{backend_name}_down"))?;
@@ -328,9 +364,35 @@ fn down_sql(&self, backend_name: &str) -> Result<Option<String>> {
         self.read_sql(backend_name, "down")
     }
 
+    fn up_data_sql(&self) -> Result<Option<String>> {
+        self.read_named_sql(UP_DATA_NAME)
+    }
+
+    fn down_data_sql(&self) -> Result<Option<String>> {
+        self.read_named_sql(DOWN_DATA_NAME)
+    }
+
     fn sql_backends(&self) -> Result<Vec<String>> {
         Ok(self.info()?.backends)
     }
+
+    fn deleted_tables(&self) -> Result<Vec<String>> {
+        self.ensure_dir()?;
+        let mut tables: Vec<String> = self
+            .fs
+            .list_dir(&self.root)?
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .file_name()?
+                    .to_string_lossy()
+                    .strip_suffix(DELETED_EXTENSION)
+                    .map(|name| name.to_string())
+            })
+            .collect();
+        tables.sort();
+        Ok(tables)
+    }
 }
 
 impl PartialEq for FsMigration {
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +16,12 @@ pub struct MemMigration {
     from: Option<String>,
     up: BTreeMap<String, String>,
     down: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    up_data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    down_data: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    deleted: BTreeSet<String>,
 }
 
 impl MemMigration {
@@ -26,6 +32,9 @@ fn new(name: String) -> Self {
             from: None,
             up: BTreeMap::new(),
             down: BTreeMap::new(),
+            up_data: None,
+            down_data: None,
+            deleted: BTreeSet::new(),
         }
     }
 }
@@ -57,6 +66,15 @@ fn down_sql(&self, backend_name: &str) -> Result<Option<String>> {
     fn sql_backends(&self) -> Result<Vec<String>> {
         Ok(self.up.keys().map(|k| k.to_string()).collect())
     }
+    fn up_data_sql(&self) -> Result<Option<String>> {
+        Ok(self.up_data.clone())
+    }
+    fn down_data_sql(&self) -> Result<Option<String>> {
+        Ok(self.down_data.clone())
+    }
+    fn deleted_tables(&self) -> Result<Vec<String>> {
+        Ok(self.deleted.iter().cloned().collect())
+    }
 }
 impl PartialEq for MemMigration {
     fn eq(&self, other: &Self) -> bool {
@@ -67,6 +85,7 @@ impl Eq for MemMigration {}
 
 impl MigrationMut for MemMigration {
     fn add_modified_table(&mut self, table: &ATable) -> Result<()> {
+        self.deleted.remove(&table.name);
         self.db.replace_table(table.clone());
         self.db.resolve_types()?;
         Ok(())
@@ -77,6 +96,7 @@ fn add_unmodified_table(&mut self, table: &ATable, from_migration_name: &str) ->
     }
     fn delete_table(&mut self, table: &str) -> Result<()> {
         self.db.remove_table(table);
+        self.deleted.insert(table.to_string());
         Ok(())
     }
     fn add_sql(&mut self, backend_name: &str, up_sql: &str, down_sql: &str) -> Result<()> {
@@ -85,6 +105,11 @@ fn add_sql(&mut self, backend_name: &str, up_sql: &str, down_sql: &str) -> Resul
             .insert(backend_name.to_string(), down_sql.to_string());
         Ok(())
     }
+    fn set_data_sql(&mut self, up_sql: Option<&str>, down_sql: Option<&str>) -> Result<()> {
+        self.up_data = up_sql.map(|s| s.to_string());
+        self.down_data = down_sql.map(|s| s.to_string());
+        Ok(())
+    }
     fn remove_sql(&mut self, backend_name: &str) -> Result<()> {
         self.up.remove(backend_name);
         self.down.remove(backend_name);
@@ -35,9 +35,30 @@ fn migration_from(&self) -> Result<Option<Cow<str>>>
     /// The names of the backends this migration has sql for.
     fn sql_backends(&self) -> Result<Vec<String>>;
 
+    /// SQL to migrate data, run after the schema changes of this
+    /// migration when it is applied, within the same transaction. It
+    /// is not generated by butane, and is used unchanged by every
+    /// backend.
+    fn up_data_sql(&self) -> Result<Option<String>>;
+
+    /// SQL to migrate data, run before the schema changes of this
+    /// migration are undone when it is downgraded, within the same
+    /// transaction.
+    fn down_data_sql(&self) -> Result<Option<String>>;
+
+    /// The names of tables explicitly deleted with
+    /// [`MigrationMut::delete_table`] and not re-added since. Only
+    /// meaningful on the special current migration.
+    fn deleted_tables(&self) -> Result<Vec<String>>;
+
     /// Apply the migration to a database connection. The connection
     /// must be for the same type of database as this and the database
-    /// must be in the state of the migration prior to this one
+    /// must be in the state of the migration prior to this one.
+    ///
+    /// The migration's SQL runs in a transaction together with
+    /// recording the migration as applied, so if any statement fails
+    /// the database is left unchanged. Both supported backends
+    /// (PostgreSQL and SQLite) support DDL within transactions.
     fn apply(&self, conn: &mut impl BackendConnection) -> Result<()> {
         let backend_name = conn.backend_name();
         let tx = conn.transaction()?;
@@ -45,6 +66,9 @@ fn apply(&self, conn: &mut impl BackendConnection) -> Result<()> {
             .up_sql(backend_name)?
             .ok_or_else(|| Error::UnknownBackend(backend_name.to_string()))?;
         tx.execute(&sql)?;
+        if let Some(data_sql) = self.up_data_sql()? {
+            tx.execute(&data_sql)?;
+        }
         self.mark_applied(&tx)?;
         tx.commit()
     }
@@ -64,13 +88,17 @@ fn mark_applied(&self, conn: &impl ConnectionMethods) -> Result<()> {
     /// Un-apply (downgrade) the migration to a database
     /// connection. The connection must be for the same type of
     /// database as this and this must be the latest migration applied
-    /// to the database.
+    /// to the database. As with [`apply`](Migration::apply), this is
+    /// done in a single transaction.
     fn downgrade(&self, conn: &mut impl BackendConnection) -> Result<()> {
         let backend_name = conn.backend_name();
         let tx = conn.transaction()?;
         let sql = self
             .down_sql(backend_name)?
             .ok_or_else(|| Error::UnknownBackend(backend_name.to_string()))?;
+        if let Some(data_sql) = self.down_data_sql()? {
+            tx.execute(&data_sql)?;
+        }
         tx.execute(&sql)?;
         let nameval = self.name().as_ref().to_sql();
         tx.delete_where(
@@ -96,8 +124,11 @@ pub trait MigrationMut: Migration {
 
     /// Delete the table with the given name. Note that simply
     /// deleting a table in code does not work -- it will remain with
-    /// its last known schema unless explicitly deleted. See also the
-    /// butane cli command `butane delete table <TABLE>`.
+    /// its last known schema unless explicitly deleted, as a model
+    /// missing from the build cannot be distinguished from one which
+    /// was removed. The deletion is recorded (see
+    /// [`Migration::deleted_tables`]) until the table is added again.
+    /// See also the butane cli command `butane delete table <TABLE>`.
     fn delete_table(&mut self, name: &str) -> Result<()>;
 
     /// Set the backend-specific commands to apply/undo this migration.
@@ -106,6 +137,10 @@ pub trait MigrationMut: Migration {
     /// Remove the backend-specific commands to apply/undo this migration.
     fn remove_sql(&mut self, backend_name: &str) -> Result<()>;
 
+    /// Set (or with `None`, remove) the SQL to migrate data when
+    /// applying/undoing this migration. See [`Migration::up_data_sql`].
+    fn set_data_sql(&mut self, up_sql: Option<&str>, down_sql: Option<&str>) -> Result<()>;
+
     /// Adds a TypeKey -> SqlType mapping. Only meaningful on the special current migration.
     fn add_type(&mut self, key: TypeKey, sqltype: DeferredSqlType) -> Result<()>;
 
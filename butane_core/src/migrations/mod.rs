@@ -27,6 +27,8 @@
 pub use fsmigrations::{FsMigration, FsMigrations};
 mod memmigrations;
 pub use memmigrations::{MemMigration, MemMigrations};
+mod verify;
+pub use verify::{verify_schema, SchemaDiscrepancy};
 
 /// A collection of migrations.
 #[allow(async_fn_in_trait)] // We don't expect to need to change the Send bounds of the future.
@@ -113,7 +115,9 @@ fn last_applied_migration(&self, conn: &impl ConnectionMethods) -> Result<Option
         Ok(None)
     }
 
-    /// Migrate connection forward.
+    /// Migrate connection forward. Each migration is applied in its
+    /// own transaction, so should one fail, the database is left in
+    /// the state of the last migration successfully applied.
     fn migrate(&self, connection: &mut impl BackendConnection) -> Result<()> {
         let to_apply = self.unapplied_migrations(connection)?;
         for migration in &to_apply {
@@ -222,10 +226,54 @@ fn create_migration(
         name: &str,
         from: Option<&Self::M>,
     ) -> Result<bool> {
-        let to_db = self.current().db()?;
+        let to_db = self.current_db_from(from)?;
         self.create_migration_to(backends, name, from, to_db)
     }
 
+    /// Get the database state a migration from `from` to `current`
+    /// would produce.
+    ///
+    /// A table which is present in `from` but not in `current` may
+    /// belong to a model which was removed, or to one which simply
+    /// was not part of the last build. To avoid silently dropping
+    /// tables, such tables are retained unless they have been
+    /// explicitly deleted with [`MigrationMut::delete_table`]. See
+    /// also [`missing_tables`](MigrationsMut::missing_tables).
+    fn current_db_from(&mut self, from: Option<&Self::M>) -> Result<ADB> {
+        let mut to_db = self.current().db()?;
+        if let Some(from) = from {
+            let deleted = self.current().deleted_tables()?;
+            for table in from.db()?.tables() {
+                if to_db.get_table(&table.name).is_none() && !deleted.contains(&table.name) {
+                    to_db.replace_table(table.clone());
+                }
+            }
+        }
+        Ok(to_db)
+    }
+
+    /// Names of the tables which are present in `from` but missing
+    /// from `current` without having been explicitly deleted. These
+    /// are retained by [`create_migration`](MigrationsMut::create_migration);
+    /// if their models were removed, delete them with
+    /// [`MigrationMut::delete_table`] to drop them in the next migration.
+    fn missing_tables(&mut self, from: Option<&Self::M>) -> Result<Vec<String>> {
+        let from = match from {
+            Some(from) => from,
+            None => return Ok(Vec::new()),
+        };
+        let current_db = self.current().db()?;
+        let deleted = self.current().deleted_tables()?;
+        Ok(from
+            .db()?
+            .tables()
+            .filter(|table| {
+                current_db.get_table(&table.name).is_none() && !deleted.contains(&table.name)
+            })
+            .map(|table| table.name.clone())
+            .collect())
+    }
+
     /// Create a migration `from` -> `to_db` named `name`. From may be None, in which
     /// case the migration is created from an empty database.
     /// Returns true if a migration was created, false if `from` and `current` represent identical states.
@@ -253,9 +301,10 @@ fn create_migration_to(
                 | Operation::AddTableIfNotExists(table) => modified_tables.push(table.name.clone()),
                 Operation::AddColumn(table_name, _) => modified_tables.push(table_name.clone()),
                 Operation::RemoveColumn(table_name, _) => modified_tables.push(table_name.clone()),
-                Operation::ChangeColumn(table_name, _, _) => {
-                    modified_tables.push(table_name.clone())
-                }
+                Operation::ChangeColumn(table_name, _, _)
+                | Operation::ChangeTableChecks(table_name, _, _)
+                | Operation::AddIndex(table_name, _)
+                | Operation::RemoveIndex(table_name, _) => modified_tables.push(table_name.clone()),
                 Operation::RemoveTable(_) | Operation::RemoveTableConstraints(_) => {}
             }
         }
@@ -331,9 +380,129 @@ pub fn copy_migration(from: &impl Migration, to: &mut impl MigrationMut) -> Resu
             to.add_sql(&backend_name, &up_sql, &down_sql)?;
         }
     }
+    to.set_data_sql(
+        from.up_data_sql()?.as_deref(),
+        from.down_data_sql()?.as_deref(),
+    )?;
     Ok(())
 }
 
+/// A migration present in only one of two compared collections, or
+/// present in both but with different contents. See [`diff_migrations`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MigrationChange {
+    /// The name of the migration.
+    pub name: String,
+    /// The schema impact. For an added or removed migration, the
+    /// operations the migration performs. For a changed migration,
+    /// the operations needed to move from the old version's resulting
+    /// schema to the new version's. May be empty for a changed
+    /// migration if only its SQL or predecessor differs.
+    pub ops: Vec<Operation>,
+}
+
+/// Differences between two collections of migrations, as returned by [`diff_migrations`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MigrationsDiff {
+    /// Migrations only in the new collection, in chain order.
+    pub added: Vec<MigrationChange>,
+    /// Migrations only in the old collection, in chain order.
+    pub removed: Vec<MigrationChange>,
+    /// Migrations in both collections whose schema, SQL or predecessor differs.
+    pub changed: Vec<MigrationChange>,
+    /// Operations to move from the schema of the latest old migration
+    /// to that of the latest new migration.
+    pub schema: Vec<Operation>,
+}
+
+impl MigrationsDiff {
+    /// Returns true if the two collections contain equivalent migrations.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.schema.is_empty()
+    }
+}
+
+/// Compares two collections of migrations, e.g. the same migrations
+/// directory before and after regenerating migrations. Only migrations
+/// reachable from each collection's latest migration are considered.
+/// The collections are equivalent if the result [`is_empty`][MigrationsDiff::is_empty].
+pub fn diff_migrations(old: &impl Migrations, new: &impl Migrations) -> Result<MigrationsDiff> {
+    let old_all = old.all_migrations()?;
+    let new_all = new.all_migrations()?;
+    let mut result = MigrationsDiff::default();
+
+    for m in &new_all {
+        match old_all.iter().find(|o| o.name() == m.name()) {
+            None => result.added.push(MigrationChange {
+                name: m.name().to_string(),
+                ops: migration_ops(new, m)?,
+            }),
+            Some(o) => {
+                let ops = adb::diff(&o.db()?, &m.db()?);
+                if !ops.is_empty() || !same_contents(o, m)? {
+                    result.changed.push(MigrationChange {
+                        name: m.name().to_string(),
+                        ops,
+                    });
+                }
+            }
+        }
+    }
+    for m in &old_all {
+        if !new_all.iter().any(|n| n.name() == m.name()) {
+            result.removed.push(MigrationChange {
+                name: m.name().to_string(),
+                ops: migration_ops(old, m)?,
+            });
+        }
+    }
+
+    let old_db = old.latest().map_or(Ok(ADB::new()), |m| m.db())?;
+    let new_db = new.latest().map_or(Ok(ADB::new()), |m| m.db())?;
+    result.schema = adb::diff(&old_db, &new_db);
+    Ok(result)
+}
+
+/// The operations performed by migration `m`, a member of `migrations`.
+fn migration_ops<Ms: Migrations>(migrations: &Ms, m: &Ms::M) -> Result<Vec<Operation>> {
+    let from_db = match m.migration_from()? {
+        None => ADB::new(),
+        Some(name) => migrations
+            .get_migration(&name)
+            .ok_or_else(|| Error::MigrationError(format!("Migration {name} not in chain")))?
+            .db()?,
+    };
+    Ok(adb::diff(&from_db, &m.db()?))
+}
+
+/// Compares everything other than the schema of two migrations with the same name.
+fn same_contents(a: &impl Migration, b: &impl Migration) -> Result<bool> {
+    if a.migration_from()? != b.migration_from()? {
+        return Ok(false);
+    }
+    let mut a_backends = a.sql_backends()?;
+    let mut b_backends = b.sql_backends()?;
+    a_backends.sort();
+    b_backends.sort();
+    if a_backends != b_backends
+        || a.up_data_sql()? != b.up_data_sql()?
+        || a.down_data_sql()? != b.down_data_sql()?
+    {
+        return Ok(false);
+    }
+    for backend in &a_backends {
+        if a.up_sql(backend)? != b.up_sql(backend)?
+            || a.down_sql(backend)? != b.down_sql(backend)?
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct ButaneMigration {
     name: String,
@@ -375,12 +544,12 @@ impl crate::internal::DataObjectInternal for ButaneMigration {
     fn pk_mut(&mut self) -> &mut impl PrimaryKeyType {
         &mut self.name
     }
-    fn non_auto_values(&self, include_pk: bool) -> Vec<SqlValRef> {
+    fn non_auto_values(&self, include_pk: bool) -> Result<Vec<SqlValRef>> {
         let mut values: Vec<SqlValRef<'_>> = Vec::with_capacity(2usize);
         if include_pk {
             values.push(self.name.to_sql_ref());
         }
-        values
+        Ok(values)
     }
     #[cfg(feature = "async")]
     async fn save_many_to_many_async(&mut self, _conn: &impl ConnectionMethodsAsync) -> Result<()> {
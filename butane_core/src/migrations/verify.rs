@@ -0,0 +1,173 @@
+//! Detection of drift between the schema described by migrations and
+//! the schema actually present in a database.
+
+use std::fmt;
+
+use super::adb::{ATable, TypeIdentifier, ADB};
+use crate::db::BackendConnection;
+use crate::Result;
+
+/// A difference between the schema expected from migrations and the
+/// schema found in the database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaDiscrepancy {
+    /// An expected table does not exist.
+    MissingTable(String),
+    /// An expected column does not exist.
+    MissingColumn { table: String, column: String },
+    /// The database has a column which the schema does not.
+    UnexpectedColumn { table: String, column: String },
+    /// A column has a different type than expected.
+    TypeMismatch {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+    /// A column differs from the schema in whether it allows null.
+    NullableMismatch {
+        table: String,
+        column: String,
+        expected: bool,
+    },
+    /// A column differs from the schema in whether it is the primary key.
+    PrimaryKeyMismatch {
+        table: String,
+        column: String,
+        expected: bool,
+    },
+}
+
+impl fmt::Display for SchemaDiscrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDiscrepancy::MissingTable(table) => write!(f, "table {table} is missing"),
+            SchemaDiscrepancy::MissingColumn { table, column } => {
+                write!(f, "column {table}.{column} is missing")
+            }
+            SchemaDiscrepancy::UnexpectedColumn { table, column } => {
+                write!(f, "column {table}.{column} is not in the schema")
+            }
+            SchemaDiscrepancy::TypeMismatch {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column {table}.{column} has type {actual}, expected {expected}"
+            ),
+            SchemaDiscrepancy::NullableMismatch {
+                table,
+                column,
+                expected,
+            } => {
+                let (expected, actual) = if *expected {
+                    ("nullable", "not nullable")
+                } else {
+                    ("not nullable", "nullable")
+                };
+                write!(
+                    f,
+                    "column {table}.{column} is {actual}, expected {expected}"
+                )
+            }
+            SchemaDiscrepancy::PrimaryKeyMismatch {
+                table,
+                column,
+                expected,
+            } => {
+                if *expected {
+                    write!(f, "column {table}.{column} is not the primary key")
+                } else {
+                    write!(f, "column {table}.{column} is unexpectedly the primary key")
+                }
+            }
+        }
+    }
+}
+
+/// Compare the tables in `expected` (typically the [`db`](super::Migration::db)
+/// of the last applied migration) against the database `conn` is connected to.
+///
+/// Tables in the database which are not in `expected` are ignored. Column
+/// names are compared case-insensitively, as some backends fold unquoted
+/// identifiers.
+pub fn verify_schema(
+    conn: &impl BackendConnection,
+    expected: &ADB,
+) -> Result<Vec<SchemaDiscrepancy>> {
+    let backend = conn.backend();
+    let mut discrepancies = Vec::new();
+    for table in expected.tables() {
+        match conn.introspect_table(&table.name)? {
+            None => discrepancies.push(SchemaDiscrepancy::MissingTable(table.name.clone())),
+            Some(actual) => {
+                compare_table(table, &actual, &*backend, &mut discrepancies)?;
+            }
+        }
+    }
+    Ok(discrepancies)
+}
+
+fn compare_table(
+    expected: &ATable,
+    actual: &ATable,
+    backend: &dyn crate::db::Backend,
+    discrepancies: &mut Vec<SchemaDiscrepancy>,
+) -> Result<()> {
+    let table = &expected.name;
+    for col in &expected.columns {
+        let Some(actual_col) = actual
+            .columns
+            .iter()
+            .find(|c| c.name().eq_ignore_ascii_case(col.name()))
+        else {
+            discrepancies.push(SchemaDiscrepancy::MissingColumn {
+                table: table.clone(),
+                column: col.name().to_string(),
+            });
+            continue;
+        };
+        let expected_ty = backend.column_type_name(col)?;
+        let actual_ty = match actual_col.typeid()? {
+            TypeIdentifier::Name(name) => name,
+            TypeIdentifier::Ty(ty) => format!("{ty:?}"),
+        };
+        if !expected_ty.eq_ignore_ascii_case(&actual_ty) {
+            discrepancies.push(SchemaDiscrepancy::TypeMismatch {
+                table: table.clone(),
+                column: col.name().to_string(),
+                expected: expected_ty,
+                actual: actual_ty,
+            });
+        }
+        if col.nullable() != actual_col.nullable() {
+            discrepancies.push(SchemaDiscrepancy::NullableMismatch {
+                table: table.clone(),
+                column: col.name().to_string(),
+                expected: col.nullable(),
+            });
+        }
+        if col.is_pk() != actual_col.is_pk() {
+            discrepancies.push(SchemaDiscrepancy::PrimaryKeyMismatch {
+                table: table.clone(),
+                column: col.name().to_string(),
+                expected: col.is_pk(),
+            });
+        }
+    }
+    for actual_col in &actual.columns {
+        if expected
+            .columns
+            .iter()
+            .all(|c| !c.name().eq_ignore_ascii_case(actual_col.name()))
+        {
+            discrepancies.push(SchemaDiscrepancy::UnexpectedColumn {
+                table: table.clone(),
+                column: actual_col.name().to_string(),
+            });
+        }
+    }
+    Ok(())
+}
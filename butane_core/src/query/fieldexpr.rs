@@ -4,7 +4,7 @@
 use std::marker::PhantomData;
 
 use crate::fkey::ForeignKey;
-use crate::query::{BoolExpr, Column, Expr, Join};
+use crate::query::{BoolExpr, Column, Expr, Join, OrderExpr};
 use crate::sqlval::{FieldType, SqlVal, ToSql};
 use crate::DataObject;
 
@@ -129,4 +129,12 @@ pub fn containspk(&self, pk: impl Borrow<<T::PKType as FieldType>::RefType>) ->
     pub fn fields(&self) -> T::Fields {
         T::Fields::default()
     }
+    /// The number of objects in the relationship, for use in
+    /// [`Query::order_by`](crate::query::Query::order_by).
+    pub fn count(&self) -> OrderExpr {
+        OrderExpr::ManyCount {
+            many_table: self.many_table,
+            owner_pk: Column::new(O::TABLE, O::PKCOL),
+        }
+    }
 }
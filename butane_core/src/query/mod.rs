@@ -77,11 +77,25 @@ pub enum OrderDirection {
     Descending,
 }
 
+/// Represents an expression which may be sorted on.
+#[derive(Clone, Debug)]
+pub enum OrderExpr {
+    /// A column, referenced by name.
+    Column(&'static str),
+    /// The number of objects related to each row through the
+    /// [`Many`](crate::many::Many) table `many_table`, where
+    /// `owner_pk` is the primary key column of the queried table.
+    ManyCount {
+        many_table: &'static str,
+        owner_pk: Column,
+    },
+}
+
 /// Represents a sorting term (ORDER BY in SQL).
 #[derive(Clone, Debug)]
 pub struct Order {
     pub direction: OrderDirection,
-    pub column: &'static str,
+    pub expr: OrderExpr,
 }
 
 #[derive(Clone, Debug)]
@@ -170,8 +184,17 @@ pub fn offset(mut self, off: i32) -> Query<T> {
     /// this method may be made, with earlier calls taking precedence.
     /// It is recommended to use the `colname!`
     /// macro to construct the column name in a type-safe manner.
-    pub fn order(mut self, column: &'static str, direction: OrderDirection) -> Query<T> {
-        self.sort.push(Order { direction, column });
+    pub fn order(self, column: &'static str, direction: OrderDirection) -> Query<T> {
+        self.order_by(OrderExpr::Column(column), direction)
+    }
+
+    /// Order the query results by the given expression, such as the
+    /// number of related objects from
+    /// [`ManyFieldExpr::count`]. Multiple calls to this method (or
+    /// [`order`](Query::order)) may be made, with earlier calls taking
+    /// precedence.
+    pub fn order_by(mut self, expr: OrderExpr, direction: OrderDirection) -> Query<T> {
+        self.sort.push(Order { direction, expr });
         self
     }
 
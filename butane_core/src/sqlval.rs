@@ -8,7 +8,7 @@
 use std::fmt;
 
 #[cfg(feature = "datetime")]
-use chrono::{naive::NaiveDateTime, DateTime};
+use chrono::{naive::NaiveDate, naive::NaiveDateTime, DateTime};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "pg")]
@@ -27,8 +27,12 @@ pub enum SqlValRef<'a> {
     Blob(&'a [u8]),
     #[cfg(feature = "json")]
     Json(serde_json::Value),
+    #[cfg(feature = "decimal")]
+    Decimal(rust_decimal::Decimal),
     #[cfg(feature = "datetime")]
     Timestamp(NaiveDateTime), // NaiveDateTime is Copy
+    #[cfg(feature = "datetime")]
+    Date(NaiveDate),
     Custom(SqlValRefCustom<'a>),
 }
 impl SqlValRef<'_> {
@@ -43,9 +47,13 @@ pub fn sqltype(&self) -> Option<SqlType> {
             SqlValRef::Text(_) => Some(SqlType::Text),
             #[cfg(feature = "datetime")]
             SqlValRef::Timestamp(_) => Some(SqlType::Timestamp),
+            #[cfg(feature = "datetime")]
+            SqlValRef::Date(_) => Some(SqlType::Date),
             SqlValRef::Blob(_) => Some(SqlType::Blob),
             #[cfg(feature = "json")]
             SqlValRef::Json(_) => Some(SqlType::Json),
+            #[cfg(feature = "decimal")]
+            SqlValRef::Decimal(_) => Some(SqlType::Decimal),
             #[cfg(feature = "pg")]
             SqlValRef::Custom(c) => match c {
                 SqlValRefCustom::PgToSql { ty, .. } => {
@@ -78,8 +86,12 @@ pub enum SqlVal {
     Blob(Vec<u8>),
     #[cfg(feature = "json")]
     Json(serde_json::Value),
+    #[cfg(feature = "decimal")]
+    Decimal(rust_decimal::Decimal),
     #[cfg(feature = "datetime")]
     Timestamp(NaiveDateTime),
+    #[cfg(feature = "datetime")]
+    Date(NaiveDate),
     Custom(Box<SqlValCustom>),
 }
 impl SqlVal {
@@ -162,9 +174,13 @@ pub fn sqltype(&self) -> Option<SqlType> {
             SqlVal::Text(_) => Some(SqlType::Text),
             #[cfg(feature = "datetime")]
             SqlVal::Timestamp(_) => Some(SqlType::Timestamp),
+            #[cfg(feature = "datetime")]
+            SqlVal::Date(_) => Some(SqlType::Date),
             SqlVal::Blob(_) => Some(SqlType::Blob),
             #[cfg(feature = "json")]
             SqlVal::Json(_) => Some(SqlType::Json),
+            #[cfg(feature = "decimal")]
+            SqlVal::Decimal(_) => Some(SqlType::Decimal),
             #[cfg(feature = "pg")]
             SqlVal::Custom(c) => match c.as_ref() {
                 SqlValCustom::Pg { ty, .. } => Some(SqlType::Custom(SqlTypeCustom::Pg(ty.clone()))),
@@ -187,8 +203,12 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             Blob(val) => f.write_str(&hex::encode(val)),
             #[cfg(feature = "json")]
             Json(val) => f.write_str(val.as_str().unwrap()),
+            #[cfg(feature = "decimal")]
+            Decimal(val) => val.fmt(f),
             #[cfg(feature = "datetime")]
             Timestamp(val) => val.format("%+").fmt(f),
+            #[cfg(feature = "datetime")]
+            Date(val) => val.fmt(f),
             Custom(val) => val.fmt(f),
         }
     }
@@ -198,6 +218,12 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 pub trait ToSql {
     fn to_sql(&self) -> SqlVal;
     fn to_sql_ref(&self) -> SqlValRef<'_>;
+    /// Like `to_sql_ref`, but fails if the value cannot be represented
+    /// in the database. Used when saving objects. The default
+    /// implementation simply calls `to_sql_ref`.
+    fn try_to_sql_ref(&self) -> Result<SqlValRef<'_>> {
+        Ok(self.to_sql_ref())
+    }
     /// The default implementation simply calls `to_sql`. Provide an
     /// alternative implementation if greater efficiency can be
     /// realized by consuming self.
@@ -253,8 +279,12 @@ fn from(valref: SqlValRef) -> SqlVal {
             Blob(v) => SqlVal::Blob(v.into()),
             #[cfg(feature = "json")]
             Json(v) => SqlVal::Json(v),
+            #[cfg(feature = "decimal")]
+            Decimal(v) => SqlVal::Decimal(v),
             #[cfg(feature = "datetime")]
             Timestamp(v) => SqlVal::Timestamp(v),
+            #[cfg(feature = "datetime")]
+            Date(v) => SqlVal::Date(v),
             Custom(v) => SqlVal::Custom(Box::new(v.into())),
         }
     }
@@ -273,8 +303,12 @@ fn from(val: &'a SqlVal) -> SqlValRef<'a> {
             Blob(v) => SqlValRef::Blob(v.as_ref()),
             #[cfg(feature = "json")]
             Json(v) => SqlValRef::Json(v.to_owned()),
+            #[cfg(feature = "decimal")]
+            Decimal(v) => SqlValRef::Decimal(*v),
             #[cfg(feature = "datetime")]
             Timestamp(v) => SqlValRef::Timestamp(*v),
+            #[cfg(feature = "datetime")]
+            Date(v) => SqlValRef::Date(*v),
             Custom(v) => SqlValRef::Custom(v.as_valref()),
         }
     }
@@ -386,6 +420,39 @@ impl PrimaryKeyType for $prim {}
 impl_prim_sql!(i64, BigInt, BigInt);
 impl_prim_sql!(i32, Int, Int);
 impl_prim_sql!(u32, BigInt, BigInt);
+
+// No backend has an unsigned 64 bit type in common, so u64 is stored
+// as a BigInt and values above i64::MAX are rejected.
+impl FromSql for u64 {
+    fn from_sql_ref(valref: SqlValRef) -> Result<Self> {
+        match valref {
+            SqlValRef::BigInt(val) => u64::try_from(val).map_err(|_| crate::Error::OutOfRange),
+            SqlValRef::Int(val) => u64::try_from(val).map_err(|_| crate::Error::OutOfRange),
+            _ => sql_conv_err!(valref, BigInt),
+        }
+    }
+}
+impl ToSql for u64 {
+    fn to_sql(&self) -> SqlVal {
+        self.to_sql_ref().into()
+    }
+    /// Values above `i64::MAX` are clamped to `i64::MAX`, which no
+    /// stored value can exceed. [`try_to_sql_ref`](ToSql::try_to_sql_ref)
+    /// is used when saving, so such values are never written.
+    fn to_sql_ref(&self) -> SqlValRef<'_> {
+        SqlValRef::BigInt(i64::try_from(*self).unwrap_or(i64::MAX))
+    }
+    fn try_to_sql_ref(&self) -> Result<SqlValRef<'_>> {
+        i64::try_from(*self)
+            .map(SqlValRef::BigInt)
+            .map_err(|_| crate::Error::OutOfRange)
+    }
+}
+impl FieldType for u64 {
+    const SQLTYPE: SqlType = SqlType::BigInt;
+    type RefType = u64;
+}
+impl PrimaryKeyType for u64 {}
 // TODO need a small int type
 impl_prim_sql!(u16, Int, Int);
 impl_prim_sql!(i16, Int, Int);
@@ -625,6 +692,9 @@ impl FieldType for DateTime<chrono::offset::Utc> {
 #[cfg(feature = "datetime")]
 impl PrimaryKeyType for DateTime<chrono::offset::Utc> {}
 
+#[cfg(feature = "datetime")]
+impl_prim_sql!(NaiveDate, Date, Date);
+
 impl ToSql for &str {
     fn to_sql(&self) -> SqlVal {
         SqlVal::Text((*self).to_string())
@@ -658,6 +728,12 @@ fn to_sql_ref(&self) -> SqlValRef<'_> {
             Some(v) => v.to_sql_ref(),
         }
     }
+    fn try_to_sql_ref(&self) -> Result<SqlValRef<'_>> {
+        match self {
+            None => Ok(SqlValRef::Null),
+            Some(v) => v.try_to_sql_ref(),
+        }
+    }
     fn into_sql(self) -> SqlVal {
         match self {
             None => SqlVal::Null,
@@ -0,0 +1,106 @@
+//! Support for the [time](https://crates.io/crates/time) crate.
+//!
+//! Values are converted to and from the chrono types used by
+//! [`SqlVal`], so the `time` feature implies `datetime`.
+
+#![deny(missing_docs)]
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::{Error, FieldType, FromSql, PrimaryKeyType, Result, SqlType, SqlVal, SqlValRef, ToSql};
+
+fn naive_date(date: Date) -> NaiveDate {
+    // time's range of years is a subset of chrono's
+    NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day() as u32).unwrap()
+}
+
+fn naive_date_time(dt: PrimitiveDateTime) -> NaiveDateTime {
+    let time = NaiveTime::from_hms_nano_opt(
+        dt.hour() as u32,
+        dt.minute() as u32,
+        dt.second() as u32,
+        dt.nanosecond(),
+    )
+    .unwrap();
+    naive_date(dt.date()).and_time(time)
+}
+
+fn date(date: NaiveDate) -> Result<Date> {
+    let month = Month::try_from(date.month() as u8).map_err(|_| Error::OutOfRange)?;
+    Date::from_calendar_date(date.year(), month, date.day() as u8).map_err(|_| Error::OutOfRange)
+}
+
+fn primitive_date_time(dt: NaiveDateTime) -> Result<PrimitiveDateTime> {
+    let time = Time::from_hms_nano(
+        dt.hour() as u8,
+        dt.minute() as u8,
+        dt.second() as u8,
+        dt.nanosecond(),
+    )
+    .map_err(|_| Error::OutOfRange)?;
+    Ok(PrimitiveDateTime::new(date(dt.date())?, time))
+}
+
+fn utc(dt: OffsetDateTime) -> PrimitiveDateTime {
+    let dt = dt.to_offset(UtcOffset::UTC);
+    PrimitiveDateTime::new(dt.date(), dt.time())
+}
+
+impl ToSql for Date {
+    fn to_sql(&self) -> SqlVal {
+        SqlVal::Date(naive_date(*self))
+    }
+    fn to_sql_ref(&self) -> SqlValRef<'_> {
+        SqlValRef::Date(naive_date(*self))
+    }
+}
+impl FromSql for Date {
+    fn from_sql_ref(valref: SqlValRef) -> Result<Self> {
+        date(NaiveDate::from_sql_ref(valref)?)
+    }
+}
+impl FieldType for Date {
+    type RefType = Self;
+    const SQLTYPE: SqlType = SqlType::Date;
+}
+impl PrimaryKeyType for Date {}
+
+impl ToSql for PrimitiveDateTime {
+    fn to_sql(&self) -> SqlVal {
+        SqlVal::Timestamp(naive_date_time(*self))
+    }
+    fn to_sql_ref(&self) -> SqlValRef<'_> {
+        SqlValRef::Timestamp(naive_date_time(*self))
+    }
+}
+impl FromSql for PrimitiveDateTime {
+    fn from_sql_ref(valref: SqlValRef) -> Result<Self> {
+        primitive_date_time(NaiveDateTime::from_sql_ref(valref)?)
+    }
+}
+impl FieldType for PrimitiveDateTime {
+    type RefType = Self;
+    const SQLTYPE: SqlType = SqlType::Timestamp;
+}
+impl PrimaryKeyType for PrimitiveDateTime {}
+
+/// Stored as UTC, as time zones are not supported. The offset is not
+/// preserved: values are loaded with a UTC offset.
+impl ToSql for OffsetDateTime {
+    fn to_sql(&self) -> SqlVal {
+        SqlVal::Timestamp(naive_date_time(utc(*self)))
+    }
+    fn to_sql_ref(&self) -> SqlValRef<'_> {
+        SqlValRef::Timestamp(naive_date_time(utc(*self)))
+    }
+}
+impl FromSql for OffsetDateTime {
+    fn from_sql_ref(valref: SqlValRef) -> Result<Self> {
+        Ok(primitive_date_time(NaiveDateTime::from_sql_ref(valref)?)?.assume_utc())
+    }
+}
+impl FieldType for OffsetDateTime {
+    type RefType = Self;
+    const SQLTYPE: SqlType = SqlType::Timestamp;
+}
+impl PrimaryKeyType for OffsetDateTime {}
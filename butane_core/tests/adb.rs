@@ -50,6 +50,66 @@ fn remove_table() {
     assert_eq!(ops, expected_ops);
 }
 
+#[test]
+fn add_and_remove_index() {
+    let mut old = ADB::default();
+    let mut table = ATable::new("a".to_owned());
+    for name in ["b", "c"] {
+        table.add_column(AColumn::new_simple(
+            name.to_owned(),
+            DeferredSqlType::KnownId(TypeIdentifier::Ty(SqlType::Text)),
+        ));
+    }
+    table.add_index(AIndex::new_default_name("a", vec!["b".to_owned()]));
+    old.replace_table(table.clone());
+
+    let mut new = ADB::default();
+    table.remove_index("a_b_idx");
+    let index = AIndex::new("a_bc", vec!["b".to_owned(), "c".to_owned()]);
+    table.add_index(index.clone());
+    new.replace_table(table);
+
+    let ops = diff(&old, &new);
+    let expected_ops = vec![
+        Operation::RemoveIndex("a".to_owned(), "a_b_idx".to_owned()),
+        Operation::AddIndex("a".to_owned(), index),
+    ];
+    assert_eq!(ops, expected_ops);
+
+    // Applying the operations produces the new schema.
+    let mut transformed = old.clone();
+    for op in ops {
+        transformed.transform_with(op);
+    }
+    assert_eq!(transformed.get_table("a"), new.get_table("a"));
+}
+
+#[test]
+fn change_checks() {
+    let mut old = ADB::default();
+    let mut table = ATable::new("a".to_owned());
+    let column = AColumn::new_simple(
+        "b".to_owned(),
+        DeferredSqlType::KnownId(TypeIdentifier::Ty(SqlType::Int)),
+    );
+    table.add_column(column.clone());
+    old.replace_table(table.clone());
+
+    let mut new = ADB::default();
+    let mut checked_column = column.clone();
+    checked_column.set_check(Some("b > 0".to_owned()));
+    table.replace_column(checked_column.clone());
+    table.checks.push("b < 10".to_owned());
+    new.replace_table(table);
+
+    let ops = diff(&old, &new);
+    let expected_ops = vec![
+        Operation::ChangeColumn("a".to_owned(), column, checked_column),
+        Operation::ChangeTableChecks("a".to_owned(), vec![], vec!["b < 10".to_owned()]),
+    ];
+    assert_eq!(ops, expected_ops);
+}
+
 #[test]
 fn stable_table_alpha_order() {
     let old = ADB::default();
@@ -1,4 +1,9 @@
-use butane_core::db::{connect_async, ConnectionAsync, ConnectionSpec};
+use butane_core::db::{
+    connect_async, BackendRows, Column, ConnectionAsync, ConnectionMethods, ConnectionMethodsAsync,
+    ConnectionSpec, SqlComment,
+};
+use butane_core::query::{BoolExpr, Expr};
+use butane_core::{SqlType, SqlVal, SqlValRef};
 use butane_test_helper::*;
 use butane_test_macros::butane_test;
 
@@ -108,3 +113,175 @@ fn saves_invalid_connection_spec_to_missing_path() {
     let loaded_spec = ConnectionSpec::load(path).unwrap();
     assert_eq!(spec, loaded_spec);
 }
+
+#[test]
+fn sql_comment_rendering() {
+    let comment = SqlComment::new()
+        .with_tag("route", "/posts")
+        .with_tag("model", "Post");
+    assert_eq!(comment.to_string(), "/*model='Post',route='%2Fposts'*/");
+    assert_eq!(SqlComment::new().to_string(), "");
+
+    // Nothing in a tag can terminate the comment early.
+    let comment = SqlComment::new().with_tag("x*/", "*/ DROP TABLE foo; --'");
+    let rendered = comment.to_string();
+    assert_eq!(rendered.matches("*/").count(), 1);
+    assert!(rendered.ends_with("*/"));
+    assert!(!rendered.contains(';'));
+}
+
+#[butane_test(nomigrate)]
+async fn sql_comment_statements_execute(mut conn: ConnectionAsync) {
+    conn.execute("CREATE TABLE tagged (id INTEGER PRIMARY KEY, name TEXT);")
+        .await
+        .unwrap();
+    conn.set_sql_comment(Some(
+        SqlComment::new()
+            .with_tag("model", "Tagged")
+            .with_tag("request_id", "*/ ?; $1 '"),
+    ));
+
+    let columns = [
+        Column::new("id", SqlType::Int),
+        Column::new("name", SqlType::Text),
+    ];
+    conn.insert_only(
+        "tagged",
+        &columns,
+        &[SqlValRef::Int(1), SqlValRef::Text("one")],
+    )
+    .await
+    .unwrap();
+    conn.update(
+        "tagged",
+        columns[0].clone(),
+        SqlValRef::Int(1),
+        &columns[1..],
+        &[SqlValRef::Text("uno")],
+    )
+    .await
+    .unwrap();
+    let mut rows = conn
+        .query(
+            "tagged",
+            &columns[1..],
+            Some(BoolExpr::Eq("id", Expr::Val(SqlVal::Int(1)))),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(
+        SqlVal::from(row.get(0, SqlType::Text).unwrap()),
+        SqlVal::Text("uno".to_string())
+    );
+    drop(rows);
+
+    if conn.backend_name() == "pg" {
+        // The comment is visible to the server as part of the statement text.
+        let mut rows = conn
+            .query(
+                "tagged",
+                &[Column::new("current_query()", SqlType::Text)],
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let row = rows.next().unwrap().unwrap();
+        let SqlValRef::Text(sql) = row.get(0, SqlType::Text).unwrap() else {
+            panic!("expected text");
+        };
+        assert!(
+            sql.starts_with("/*model='Tagged',request_id='%2A%2F%20%3F%3B%20%241%20%27'*/ SELECT")
+        );
+    }
+
+    // Transactions inherit the comment.
+    let tr = conn.transaction().await.unwrap();
+    tr.delete("tagged", "id", SqlVal::Int(1)).await.unwrap();
+    tr.commit().await.unwrap();
+    assert!(!conn.has_table("missing").await.unwrap());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_pragmas_from_path() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("pragmas.db");
+    let spec = ConnectionSpec::new(
+        "sqlite",
+        format!(
+            "{}?journal_mode=WAL&busy_timeout=1234&foreign_keys=OFF",
+            path.display()
+        ),
+    );
+    let conn = butane_core::db::connect(&spec).unwrap();
+    let pragma = |name: &str, column: &'static str, ty: SqlType| {
+        let mut rows = conn
+            .query(
+                &format!("pragma_{name}"),
+                &[Column::new(column, ty.clone())],
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        SqlVal::from(rows.next().unwrap().unwrap().get(0, ty).unwrap())
+    };
+    assert_eq!(
+        pragma("journal_mode", "journal_mode", SqlType::Text),
+        SqlVal::Text("wal".to_string())
+    );
+    assert_eq!(
+        pragma("busy_timeout", "timeout", SqlType::BigInt),
+        SqlVal::BigInt(1234)
+    );
+    assert_eq!(
+        pragma("foreign_keys", "foreign_keys", SqlType::BigInt),
+        SqlVal::BigInt(0)
+    );
+    // The parameters are not part of the file name.
+    assert!(path.exists());
+
+    let spec = ConnectionSpec::new("sqlite", format!("{}?synchronous=OFF;DROP", path.display()));
+    assert!(matches!(
+        butane_core::db::connect(&spec),
+        Err(butane_core::Error::InvalidConnectionParam(_))
+    ));
+}
+
+#[butane_test(nomigrate)]
+async fn statement_timeout(mut conn: ConnectionAsync) {
+    let slow_sql = match conn.backend_name() {
+        "pg" => "SELECT pg_sleep(10);",
+        _ => {
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 1000000000) \
+             SELECT count(*) FROM c;"
+        }
+    };
+    conn.set_statement_timeout(Some(std::time::Duration::from_millis(50)))
+        .await
+        .unwrap();
+    let result = conn.execute(slow_sql).await;
+    assert!(
+        matches!(result, Err(butane_core::Error::Timeout)),
+        "{result:?}"
+    );
+    // The connection remains usable for statements within the limit.
+    conn.execute("SELECT 1;").await.unwrap();
+
+    // Transactions inherit the limit.
+    let tr = conn.transaction().await.unwrap();
+    let result = tr.execute(slow_sql).await;
+    assert!(matches!(result, Err(butane_core::Error::Timeout)));
+    tr.rollback().await.unwrap();
+
+    conn.set_statement_timeout(None).await.unwrap();
+    conn.execute("SELECT 1;").await.unwrap();
+}
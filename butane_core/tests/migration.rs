@@ -1,7 +1,10 @@
 use butane_core::codegen::{butane_type_with_migrations, model_with_migrations};
 use butane_core::db::{BackendConnection, Connection};
-use butane_core::migrations::adb::{DeferredSqlType, TypeIdentifier, TypeKey};
-use butane_core::migrations::{MemMigrations, Migration, MigrationMut, Migrations, MigrationsMut};
+use butane_core::migrations::adb::{DeferredSqlType, Operation, TypeIdentifier, TypeKey};
+use butane_core::migrations::{
+    diff_migrations, verify_schema, MemMigrations, Migration, MigrationMut, Migrations,
+    MigrationsMut, SchemaDiscrepancy,
+};
 use butane_core::{SqlType, SqlVal};
 #[cfg(feature = "pg")]
 use butane_test_helper::pg_connection;
@@ -97,6 +100,63 @@ struct Foo {
     assert_eq!(*barcol.default(), Some(SqlVal::Text("turtle".to_string())));
 }
 
+#[test]
+fn current_migration_index_attribute() {
+    let tokens = quote! {
+        #[derive(PartialEq, Eq, Debug, Clone)]
+        #[index(name = "foo_bar_baz", fields = ["bar", "baz"])]
+        #[index(fields = ["baz"])]
+        struct Foo {
+            id: i64,
+            #[index]
+            bar: String,
+            baz: i32,
+        }
+    };
+
+    let mut ms = MemMigrations::new();
+    model_with_migrations(tokens, &mut ms);
+    let m = ms.current();
+    let db = m.db().unwrap();
+    let table = db.get_table("Foo").expect("No Foo table");
+    assert_eq!(table.indexes.len(), 3);
+    assert_eq!(
+        table.index("Foo_bar_idx").unwrap().columns,
+        vec!["bar".to_string()]
+    );
+    assert_eq!(
+        table.index("foo_bar_baz").unwrap().columns,
+        vec!["bar".to_string(), "baz".to_string()]
+    );
+    assert_eq!(
+        table.index("Foo_baz_idx").unwrap().columns,
+        vec!["baz".to_string()]
+    );
+}
+
+#[test]
+fn current_migration_check_attribute() {
+    let tokens = quote! {
+        #[derive(PartialEq, Eq, Debug, Clone)]
+        #[check = "likes <= views"]
+        struct Foo {
+            id: i64,
+            #[check = "likes >= 0"]
+            likes: i32,
+            views: i32,
+        }
+    };
+
+    let mut ms = MemMigrations::new();
+    model_with_migrations(tokens, &mut ms);
+    let m = ms.current();
+    let db = m.db().unwrap();
+    let table = db.get_table("Foo").expect("No Foo table");
+    assert_eq!(table.column("likes").unwrap().check(), Some("likes >= 0"));
+    assert_eq!(table.column("views").unwrap().check(), None);
+    assert_eq!(table.checks, vec!["likes <= views".to_string()]);
+}
+
 #[test]
 fn current_migration_auto_attribute() {
     let tokens = quote! {
@@ -325,12 +385,538 @@ fn migration_delete_table_pg() {
     );
 }
 
+#[cfg(feature = "sqlite")]
+#[test]
+fn migration_add_index_sqlite() {
+    migration_add_index(
+        &mut sqlite_connection(),
+        "CREATE INDEX Foo_bar_idx ON Foo (bar);",
+        "DROP INDEX Foo_bar_idx;",
+    );
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn migration_add_index_pg() {
+    let (mut conn, _data) = pg_connection();
+    migration_add_index(
+        &mut conn,
+        "CREATE INDEX Foo_bar_idx ON Foo (bar);",
+        "DROP INDEX Foo_bar_idx;",
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn migration_add_field_indexed_sqlite() {
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            #[index]
+            bar: String,
+        }
+    };
+
+    let v2 = quote! {
+        struct Foo {
+            id: i64,
+            #[index]
+            bar: String,
+            baz: u32,
+        }
+    };
+    // Recreating the table when dropping the column must also
+    // recreate its index.
+    test_migrate(
+        &mut sqlite_connection(),
+        init,
+        v2,
+        "ALTER TABLE Foo ADD COLUMN baz INTEGER NOT NULL DEFAULT 0;",
+        "CREATE TABLE Foo__butane_tmp (id INTEGER NOT NULL PRIMARY KEY,bar TEXT NOT NULL);
+INSERT INTO Foo__butane_tmp SELECT id, bar FROM Foo;DROP TABLE Foo;
+ALTER TABLE Foo__butane_tmp RENAME TO Foo;
+CREATE INDEX Foo_bar_idx ON Foo (bar);",
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn migration_add_check_sqlite() {
+    let mut conn = sqlite_connection();
+    migration_add_check(
+        &mut conn,
+        // See comments on migration_add_field_sqlite
+        "CREATE TABLE Foo__butane_tmp (id INTEGER NOT NULL PRIMARY KEY,likes INTEGER NOT NULL CHECK (likes >= 0));
+INSERT INTO Foo__butane_tmp SELECT id, likes FROM Foo;DROP TABLE Foo;
+ALTER TABLE Foo__butane_tmp RENAME TO Foo;",
+        "CREATE TABLE Foo__butane_tmp (id INTEGER NOT NULL PRIMARY KEY,likes INTEGER NOT NULL);
+INSERT INTO Foo__butane_tmp SELECT id, likes FROM Foo;DROP TABLE Foo;
+ALTER TABLE Foo__butane_tmp RENAME TO Foo;",
+    );
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn migration_add_check_pg() {
+    let (mut conn, _data) = pg_connection();
+    migration_add_check(
+        &mut conn,
+        "ALTER TABLE Foo ADD CONSTRAINT Foo_likes_check CHECK (likes >= 0);",
+        "ALTER TABLE Foo DROP CONSTRAINT Foo_likes_check;",
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn migration_add_table_check_sqlite() {
+    let mut conn = sqlite_connection();
+    migration_add_table_check(
+        &mut conn,
+        // See comments on migration_add_field_sqlite
+        "CREATE TABLE Foo__butane_tmp (id INTEGER NOT NULL PRIMARY KEY,likes INTEGER NOT NULL,views INTEGER NOT NULL,CHECK (likes <= views));
+INSERT INTO Foo__butane_tmp SELECT id, likes, views FROM Foo;DROP TABLE Foo;
+ALTER TABLE Foo__butane_tmp RENAME TO Foo;",
+        "CREATE TABLE Foo__butane_tmp (id INTEGER NOT NULL PRIMARY KEY,likes INTEGER NOT NULL,views INTEGER NOT NULL);
+INSERT INTO Foo__butane_tmp SELECT id, likes, views FROM Foo;DROP TABLE Foo;
+ALTER TABLE Foo__butane_tmp RENAME TO Foo;",
+    );
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn migration_add_table_check_pg() {
+    let (mut conn, _data) = pg_connection();
+    migration_add_table_check(
+        &mut conn,
+        "ALTER TABLE Foo ADD CONSTRAINT Foo_check0 CHECK (likes <= views);",
+        "ALTER TABLE Foo DROP CONSTRAINT Foo_check0;",
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn failed_migration_rolled_back_sqlite() {
+    failed_migration_rolled_back(&mut sqlite_connection());
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn failed_migration_rolled_back_pg() {
+    let (mut conn, _data) = pg_connection();
+    failed_migration_rolled_back(&mut conn);
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn migration_data_sql_sqlite() {
+    migration_data_sql(&mut sqlite_connection());
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn migration_data_sql_pg() {
+    let (mut conn, _data) = pg_connection();
+    migration_data_sql(&mut conn);
+}
+
+#[test]
+fn data_sql_kept_by_delete_db() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let ms = butane_core::migrations::from_root(dir.path());
+    let mut m = ms.new_migration("v1");
+    m.set_migration_from(None).unwrap();
+    m.set_data_sql(Some("UPDATE Foo SET bar = 'x';"), None)
+        .unwrap();
+    assert_eq!(
+        m.up_data_sql().unwrap().as_deref(),
+        Some("UPDATE Foo SET bar = 'x';\n")
+    );
+    assert_eq!(m.down_data_sql().unwrap(), None);
+
+    // Regenerating a migration must not lose its hand-written data SQL.
+    m.delete_db().unwrap();
+    assert!(m.up_data_sql().unwrap().is_some());
+
+    m.set_data_sql(None, None).unwrap();
+    assert_eq!(m.up_data_sql().unwrap(), None);
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn missing_model_retained_mem() {
+    missing_model_retained(&mut MemMigrations::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn missing_model_retained_fs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    missing_model_retained(&mut butane_core::migrations::from_root(dir.path()));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn schema_drift_detected_sqlite() {
+    schema_drift_detected(&mut sqlite_connection());
+}
+
+#[cfg(feature = "pg")]
+#[test]
+fn schema_drift_detected_pg() {
+    let (mut conn, _data) = pg_connection();
+    schema_drift_detected(&mut conn);
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn mysql_migration_sql() {
+    let backends = nonempty::nonempty![butane_core::db::get_backend("mysql").unwrap()];
+    let mut ms = MemMigrations::new();
+    let init = quote! {
+        struct Foo {
+            id: AutoPk<i64>,
+            #[unique]
+            name: String,
+            bio: Option<String>,
+        }
+    };
+    model_with_migrations(init, &mut ms);
+    assert!(ms.create_migration(&backends, "init", None).unwrap());
+    let up_sql = ms.latest().unwrap().up_sql("mysql").unwrap().unwrap();
+    assert_eq!(
+        up_sql.lines().collect::<Vec<_>>(),
+        vec![
+            "CREATE TABLE `Foo` (",
+            "`id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,",
+            "`name` VARCHAR(255) NOT NULL UNIQUE,",
+            "`bio` TEXT",
+            ") ENGINE=InnoDB;",
+            "CREATE TABLE IF NOT EXISTS `butane_migrations` (",
+            "`name` VARCHAR(255) NOT NULL PRIMARY KEY",
+            ") ENGINE=InnoDB;",
+        ]
+    );
+
+    let v2 = quote! {
+        struct Foo {
+            id: AutoPk<i64>,
+            #[unique]
+            name: String,
+            #[index]
+            bio: Option<String>,
+            #[check = "likes >= 0"]
+            likes: i32,
+        }
+    };
+    model_with_migrations(v2, &mut ms);
+    assert!(ms
+        .create_migration(&backends, "v2", ms.latest().as_ref())
+        .unwrap());
+    let v2 = ms.latest().unwrap();
+    assert_eq!(
+        v2.up_sql("mysql")
+            .unwrap()
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>(),
+        vec![
+            "ALTER TABLE `Foo` ADD COLUMN `likes` INT NOT NULL DEFAULT (0);",
+            "ALTER TABLE `Foo` ADD CONSTRAINT `Foo_likes_check` CHECK (likes >= 0);",
+            "CREATE INDEX `Foo_bio_idx` ON `Foo` (`bio`(255));",
+        ]
+    );
+    assert_eq!(
+        v2.down_sql("mysql")
+            .unwrap()
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>(),
+        vec![
+            "DROP INDEX `Foo_bio_idx` ON `Foo`;",
+            "ALTER TABLE `Foo` DROP CONSTRAINT `Foo_likes_check`;",
+            "ALTER TABLE `Foo` DROP COLUMN `likes`;",
+        ]
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn diff_migrations_add_field() {
+    let backends = nonempty::nonempty![butane_core::db::get_backend("sqlite").unwrap()];
+    let mut ms = MemMigrations::new();
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            bar: String,
+        }
+    };
+    model_with_migrations(init, &mut ms);
+    assert!(ms.create_migration(&backends, "init", None).unwrap());
+    let before = ms.clone();
+    assert!(diff_migrations(&before, &ms).unwrap().is_empty());
+
+    let v2 = quote! {
+        struct Foo {
+            id: i64,
+            bar: String,
+            baz: u32,
+        }
+    };
+    model_with_migrations(v2, &mut ms);
+    assert!(ms
+        .create_migration(&backends, "v2", ms.latest().as_ref())
+        .unwrap());
+
+    let diff = diff_migrations(&before, &ms).unwrap();
+    assert!(!diff.is_empty());
+    assert!(diff.removed.is_empty());
+    assert!(diff.changed.is_empty());
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].name, "v2");
+    assert!(matches!(
+        diff.added[0].ops.as_slice(),
+        [Operation::AddColumn(table, col)] if table == "Foo" && col.name() == "baz"
+    ));
+    assert_eq!(diff.schema, diff.added[0].ops);
+
+    // The reverse comparison reports the migration as removed.
+    let diff = diff_migrations(&ms, &before).unwrap();
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.len(), 1);
+    assert!(matches!(
+        diff.schema.as_slice(),
+        [Operation::RemoveColumn(table, col)] if table == "Foo" && col == "baz"
+    ));
+}
+
+/// A migration which fails partway through leaves no trace.
+fn failed_migration_rolled_back(conn: &mut Connection) {
+    use butane_core::db::ConnectionMethods;
+
+    let mut ms = MemMigrations::new();
+    let backend = conn.backend();
+    let backend_name = backend.name();
+    // Lowercase table names are used as postgres folds unquoted names.
+    let init = quote! {
+        #[table = "foo"]
+        struct Foo {
+            id: i64,
+        }
+    };
+    model_with_migrations(init, &mut ms);
+    assert!(ms
+        .create_migration(&nonempty::nonempty![backend], "init", None)
+        .unwrap());
+    let init = ms.latest().unwrap();
+
+    let mut broken = ms.new_migration("broken");
+    broken.set_migration_from(Some("init".to_string())).unwrap();
+    broken
+        .add_sql(
+            backend_name,
+            "CREATE TABLE bar (id INTEGER);\nNOT VALID SQL;",
+            "DROP TABLE bar;",
+        )
+        .unwrap();
+    ms.add_migration(broken).unwrap();
+
+    assert!(ms.migrate(conn).is_err());
+    assert_eq!(ms.last_applied_migration(conn).unwrap(), Some(init));
+    assert!(conn.has_table("foo").unwrap());
+    assert!(!conn.has_table("bar").unwrap());
+}
+
+/// Populates a new column from an existing one with a data migration,
+/// and restores the old column from it when downgrading.
+fn migration_data_sql(conn: &mut Connection) {
+    use butane_core::db::{BackendRows, Column, ConnectionMethods};
+
+    let mut ms = MemMigrations::new();
+    let backends = nonempty::nonempty![conn.backend()];
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            bar: String,
+        }
+    };
+    model_with_migrations(init, &mut ms);
+    assert!(ms.create_migration(&backends, "init", None).unwrap());
+    ms.migrate(conn).unwrap();
+    conn.execute("INSERT INTO Foo (id, bar) VALUES (1, 'hello');")
+        .unwrap();
+
+    let v2 = quote! {
+        struct Foo {
+            id: i64,
+            bar: String,
+            baz: i64,
+        }
+    };
+    model_with_migrations(v2, &mut ms);
+    assert!(ms
+        .create_migration(&backends, "v2", ms.latest().as_ref())
+        .unwrap());
+    let mut v2 = ms.latest().unwrap();
+    v2.set_data_sql(
+        Some("UPDATE Foo SET baz = LENGTH(bar);"),
+        Some("UPDATE Foo SET bar = CAST(baz AS TEXT);"),
+    )
+    .unwrap();
+    ms.add_migration(v2.clone()).unwrap();
+
+    ms.migrate(conn).unwrap();
+    let mut rows = conn
+        .query(
+            "Foo",
+            &[Column::new("baz", SqlType::BigInt)],
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(
+        SqlVal::from(row.get(0, SqlType::BigInt).unwrap()),
+        SqlVal::BigInt(5)
+    );
+    drop(rows);
+
+    v2.downgrade(conn).unwrap();
+    let mut rows = conn
+        .query(
+            "Foo",
+            &[Column::new("bar", SqlType::Text)],
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(
+        SqlVal::from(row.get(0, SqlType::Text).unwrap()),
+        SqlVal::Text("5".to_string())
+    );
+}
+
+/// A table whose model is missing from the build is only removed once
+/// explicitly deleted.
+#[cfg(feature = "sqlite")]
+fn missing_model_retained<M: MigrationMut>(ms: &mut impl MigrationsMut<M = M>) {
+    let backends = nonempty::nonempty![butane_core::db::get_backend("sqlite").unwrap()];
+    let foo_model = quote! {
+        struct Foo {
+            id: i64,
+        }
+    };
+    let bar_model = quote! {
+        struct Bar {
+            id: i64,
+        }
+    };
+    model_with_migrations(foo_model.clone(), ms);
+    model_with_migrations(bar_model.clone(), ms);
+    assert!(ms.create_migration(&backends, "init", None).unwrap());
+
+    // Rebuild with only Foo, as if Bar was not compiled.
+    ms.clear_current().unwrap();
+    model_with_migrations(foo_model, ms);
+    let latest = ms.latest();
+    assert_eq!(ms.missing_tables(latest.as_ref()).unwrap(), vec!["Bar"]);
+    assert!(ms
+        .current_db_from(latest.as_ref())
+        .unwrap()
+        .get_table("Bar")
+        .is_some());
+    assert!(!ms
+        .create_migration(&backends, "v2", latest.as_ref())
+        .unwrap());
+
+    // Re-adding a deleted model cancels the deletion.
+    ms.current().delete_table("Bar").unwrap();
+    assert_eq!(ms.current().deleted_tables().unwrap(), vec!["Bar"]);
+    model_with_migrations(bar_model, ms);
+    assert!(ms.current().deleted_tables().unwrap().is_empty());
+
+    ms.current().delete_table("Bar").unwrap();
+    assert!(ms.missing_tables(latest.as_ref()).unwrap().is_empty());
+    assert!(ms
+        .create_migration(&backends, "v2", latest.as_ref())
+        .unwrap());
+    let v2 = ms.latest().unwrap();
+    assert!(v2.db().unwrap().get_table("Bar").is_none());
+    let up_sql = v2.up_sql("sqlite").unwrap().unwrap();
+    assert!(up_sql.contains("DROP TABLE Bar;"), "{up_sql}");
+}
+
+/// Changes made to the database behind the back of migrations are
+/// reported by `verify_schema`.
+fn schema_drift_detected(conn: &mut Connection) {
+    let mut ms = MemMigrations::new();
+    let backends = nonempty::nonempty![conn.backend()];
+    let tokens = quote! {
+        struct Foo {
+            #[auto]
+            id: i64,
+            bar: String,
+            baz: Option<f64>,
+        }
+    };
+    model_with_migrations(tokens, &mut ms);
+    let tokens = quote! {
+        struct Qux {
+            id: String,
+        }
+    };
+    model_with_migrations(tokens, &mut ms);
+    assert!(ms.create_migration(&backends, "init", None).unwrap());
+    ms.migrate(conn).unwrap();
+
+    let db = ms.latest().unwrap().db().unwrap();
+    assert_eq!(verify_schema(conn, &db).unwrap(), vec![]);
+
+    conn.execute("ALTER TABLE Foo ADD COLUMN extra TEXT;")
+        .unwrap();
+    conn.execute("DROP TABLE Qux;").unwrap();
+    assert_eq!(
+        verify_schema(conn, &db).unwrap(),
+        vec![
+            SchemaDiscrepancy::UnexpectedColumn {
+                table: "Foo".to_string(),
+                column: "extra".to_string(),
+            },
+            SchemaDiscrepancy::MissingTable("Qux".to_string()),
+        ]
+    );
+}
+
 fn test_migrate(
     conn: &mut Connection,
     init_tokens: TokenStream,
     v2_tokens: TokenStream,
     expected_up_sql: &str,
     expected_down_sql: &str,
+) {
+    test_migrate_with(
+        conn,
+        init_tokens,
+        v2_tokens,
+        expected_up_sql,
+        expected_down_sql,
+        |_| {},
+    )
+}
+
+/// Like [test_migrate], additionally calling `check_migrated` with the
+/// connection once both migrations are applied.
+fn test_migrate_with(
+    conn: &mut Connection,
+    init_tokens: TokenStream,
+    v2_tokens: TokenStream,
+    expected_up_sql: &str,
+    expected_down_sql: &str,
+    check_migrated: impl FnOnce(&mut Connection),
 ) {
     let mut ms = MemMigrations::new();
     let backend = conn.backend();
@@ -351,6 +937,8 @@ fn test_migrate(
     let to_apply = ms.unapplied_migrations(conn).unwrap();
     assert_eq!(to_apply.len(), 0);
 
+    check_migrated(conn);
+
     verify_sql(conn, &ms, expected_up_sql, expected_down_sql);
 
     // Now downgrade, just to make sure we can
@@ -399,6 +987,74 @@ struct Foo {
     test_migrate(conn, init, v2, up_sql, down_sql);
 }
 
+fn migration_add_index(conn: &mut Connection, up_sql: &str, down_sql: &str) {
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            bar: String,
+        }
+    };
+
+    let v2 = quote! {
+        struct Foo {
+            id: i64,
+            #[index]
+            bar: String,
+        }
+    };
+    test_migrate(conn, init, v2, up_sql, down_sql);
+}
+
+fn migration_add_check(conn: &mut Connection, up_sql: &str, down_sql: &str) {
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            likes: i32,
+        }
+    };
+
+    let v2 = quote! {
+        struct Foo {
+            id: i64,
+            #[check = "likes >= 0"]
+            likes: i32,
+        }
+    };
+    test_migrate_with(conn, init, v2, up_sql, down_sql, |conn| {
+        conn.execute("INSERT INTO Foo (id, likes) VALUES (1, 1);")
+            .unwrap();
+        assert!(conn
+            .execute("INSERT INTO Foo (id, likes) VALUES (2, -1);")
+            .is_err());
+    });
+}
+
+fn migration_add_table_check(conn: &mut Connection, up_sql: &str, down_sql: &str) {
+    let init = quote! {
+        struct Foo {
+            id: i64,
+            likes: i32,
+            views: i32,
+        }
+    };
+
+    let v2 = quote! {
+        #[check = "likes <= views"]
+        struct Foo {
+            id: i64,
+            likes: i32,
+            views: i32,
+        }
+    };
+    test_migrate_with(conn, init, v2, up_sql, down_sql, |conn| {
+        conn.execute("INSERT INTO Foo (id, likes, views) VALUES (1, 1, 2);")
+            .unwrap();
+        assert!(conn
+            .execute("INSERT INTO Foo (id, likes, views) VALUES (2, 3, 2);")
+            .is_err());
+    });
+}
+
 fn migration_add_field_with_default(conn: &mut Connection, up_sql: &str, down_sql: &str) {
     let init = quote! {
         struct Foo {
@@ -0,0 +1,7 @@
+CREATE TABLE Note (
+id BIGSERIAL NOT NULL PRIMARY KEY,
+"text" TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS butane_migrations (
+"name" TEXT NOT NULL PRIMARY KEY
+);
@@ -0,0 +1,69 @@
+//! Butane migrations embedded in Rust.
+
+use butane::migrations::MemMigrations;
+
+/// Load the butane migrations embedded in Rust.
+pub fn get_migrations() -> Result<MemMigrations, butane::Error> {
+    let json = r#"{
+  "migrations": {
+    "20261017_035806568_init": {
+      "name": "20261017_035806568_init",
+      "db": {
+        "tables": {
+          "Note": {
+            "name": "Note",
+            "columns": [
+              {
+                "name": "id",
+                "sqltype": {
+                  "KnownId": {
+                    "Ty": "BigInt"
+                  }
+                },
+                "nullable": false,
+                "pk": true,
+                "auto": true,
+                "unique": false,
+                "default": null
+              },
+              {
+                "name": "text",
+                "sqltype": {
+                  "KnownId": {
+                    "Ty": "Text"
+                  }
+                },
+                "nullable": false,
+                "pk": false,
+                "auto": false,
+                "unique": false,
+                "default": null
+              }
+            ]
+          }
+        },
+        "extra_types": {}
+      },
+      "from": null,
+      "up": {
+        "pg": "CREATE TABLE Note (\nid BIGSERIAL NOT NULL PRIMARY KEY,\n\"text\" TEXT NOT NULL\n);\nCREATE TABLE IF NOT EXISTS butane_migrations (\n\"name\" TEXT NOT NULL PRIMARY KEY\n);\n"
+      },
+      "down": {
+        "pg": "DROP TABLE Note;\n"
+      }
+    }
+  },
+  "current": {
+    "name": "current",
+    "db": {
+      "tables": {},
+      "extra_types": {}
+    },
+    "from": null,
+    "up": {},
+    "down": {}
+  },
+  "latest": "20261017_035806568_init"
+}"#;
+    MemMigrations::from_json(json)
+}
@@ -0,0 +1,21 @@
+//! Helpers for the axum_pool example.
+
+#![deny(missing_docs)]
+
+pub mod butane_migrations;
+pub mod models;
+
+use butane::db::{ConnectionManager, ConnectionSpec, Pool};
+use butane::migrations::Migrations;
+
+/// Create a [Pool] of native async connections, migrating the database first.
+pub async fn create_pool(spec: ConnectionSpec) -> Pool {
+    let pool = Pool::builder(ConnectionManager::new(spec))
+        .max_size(8)
+        .build()
+        .unwrap();
+    let mut conn = pool.get().await.unwrap();
+    let migrations = butane_migrations::get_migrations().unwrap();
+    migrations.migrate_async(&mut conn).await.unwrap();
+    pool
+}
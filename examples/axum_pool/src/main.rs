@@ -0,0 +1,50 @@
+//! A small axum service sharing a deadpool [Pool] of butane connections
+//! between requests, so that handlers don't pay connection setup cost.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use axum_pool::models::Note;
+use butane::db::{ConnectionSpec, Pool};
+use butane::prelude_async::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct NewNote {
+    text: String,
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+async fn list_notes(State(pool): State<Pool>) -> Result<Json<Vec<Note>>, (StatusCode, String)> {
+    let conn = pool.get().await.map_err(internal_error)?;
+    let notes = Note::query().load(&*conn).await.map_err(internal_error)?;
+    Ok(Json(notes))
+}
+
+async fn create_note(
+    State(pool): State<Pool>,
+    Json(new_note): Json<NewNote>,
+) -> Result<(StatusCode, Json<Note>), (StatusCode, String)> {
+    let conn = pool.get().await.map_err(internal_error)?;
+    let mut note = Note::new(new_note.text);
+    note.save(&*conn).await.map_err(internal_error)?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+#[tokio::main]
+async fn main() {
+    let spec = ConnectionSpec::load(".butane/connection.json").unwrap();
+    let pool = axum_pool::create_pool(spec).await;
+    let app = Router::new()
+        .route("/notes", get(list_notes).post(create_note))
+        .with_state(pool);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    println!("Listening on http://127.0.0.1:3000");
+    axum::serve(listener, app).await.unwrap();
+}
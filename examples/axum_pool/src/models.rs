@@ -0,0 +1,23 @@
+//! Models for the axum_pool example.
+
+use butane::{model, AutoPk};
+use serde::{Deserialize, Serialize};
+
+/// A short note.
+#[model]
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Note {
+    /// Id of the note.
+    pub id: AutoPk<i64>,
+    /// Text of the note.
+    pub text: String,
+}
+impl Note {
+    /// Create a new Note.
+    pub fn new(text: impl Into<String>) -> Self {
+        Note {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+}